@@ -0,0 +1,61 @@
+//! Shrink an `.editorconfig` file while a command still reproduces a bug with it.
+//!
+//! ```sh
+//! editorconfig-reduce big.editorconfig ./reproduces.sh
+//! ```
+//!
+//! The command runs once per candidate, with the candidate on stdin, and the bug counts as
+//! reproduced when it exits successfully. The reduced config is written to stdout.
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, ExitCode, Stdio},
+};
+
+use editorconfig_parser::reduce::reduce;
+
+fn main() -> ExitCode {
+    let mut args = env::args_os().skip(1);
+    let (Some(file), Some(command)) = (args.next().map(PathBuf::from), args.next()) else {
+        let _ = writeln!(io::stderr(), "usage: editorconfig-reduce <FILE> <COMMAND> [ARGS]...");
+        return ExitCode::from(2);
+    };
+    let args: Vec<_> = args.collect();
+    let source_text = match fs::read_to_string(&file) {
+        Ok(source_text) => source_text,
+        Err(err) => {
+            let _ = writeln!(io::stderr(), "cannot read `{}`: {err}", file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let reproduces = |candidate: &str| {
+        let Ok(mut child) = Command::new(&command)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(candidate.as_bytes());
+        }
+        child.wait().is_ok_and(|status| status.success())
+    };
+    let Some(reduced) = reduce(&source_text, reproduces) else {
+        let _ = writeln!(
+            io::stderr(),
+            "`{}` does not exit successfully with `{}` on stdin",
+            command.display(),
+            file.display()
+        );
+        return ExitCode::FAILURE;
+    };
+    if io::stdout().write_all(reduced.as_bytes()).is_err() {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,197 @@
+use std::{fmt, sync::OnceLock};
+
+use crate::{
+    Charset, EditorConfig, EditorConfigGlob, EditorConfigProperties, EditorConfigProperty,
+    EditorConfigSection, EndOfLine, IndentSize, IndentStyle, MaxLineLength, PropertyKey,
+    cache::MergeCache,
+    diagnostic::{self, Message},
+};
+
+/// Builds an [EditorConfig] programmatically.
+///
+/// Unlike [EditorConfig::parse], globs and values are validated as they are added,
+/// so generated configs fail fast instead of silently ignoring bad input.
+#[derive(Debug, Default, Clone)]
+pub struct EditorConfigBuilder {
+    config: EditorConfig,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BuildError {
+    /// The section name is not a valid glob.
+    InvalidGlob { pattern: String, reason: String },
+    /// The value is not valid for a known property.
+    InvalidValue { key: String, value: String },
+    /// A property was added before any section.
+    NoSection { key: String },
+}
+
+impl Message for BuildError {
+    fn id(&self) -> &'static str {
+        match self {
+            Self::InvalidGlob { .. } => "invalid-glob",
+            Self::InvalidValue { .. } => "invalid-value",
+            Self::NoSection { .. } => "no-section",
+        }
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::InvalidGlob { pattern, reason } => {
+                vec![("pattern", pattern.clone()), ("reason", reason.clone())]
+            }
+            Self::InvalidValue { key, value } => {
+                vec![("key", key.clone()), ("value", value.clone())]
+            }
+            Self::NoSection { key } => vec![("key", key.clone())],
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&diagnostic::render(self, diagnostic::english))
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl EditorConfigBuilder {
+    pub fn root(mut self, root: bool) -> Self {
+        self.config.root = Some(root);
+        self
+    }
+
+    /// Start a new section. Subsequent properties are added to this section.
+    ///
+    /// # Errors
+    ///
+    /// Returns [BuildError::InvalidGlob] if `name` is not a valid glob.
+    pub fn section(mut self, name: &str) -> Result<Self, BuildError> {
+        let matcher = EditorConfigGlob::new(name).map_err(|err| BuildError::InvalidGlob {
+            pattern: err.pattern,
+            reason: err.kind.to_string(),
+        })?;
+        self.config.sections.push(EditorConfigSection {
+            name: name.to_string(),
+            matcher: Some(matcher),
+            ..EditorConfigSection::default()
+        });
+        Ok(self)
+    }
+
+    /// Add a property to the current section.
+    ///
+    /// # Errors
+    ///
+    /// * [BuildError::NoSection] if no section has been started.
+    /// * [BuildError::InvalidValue] if `key` is a known property and `value` is not valid for it,
+    ///   including values outside the specification that parsing keeps as `Other`.
+    pub fn property(mut self, key: &str, value: &str) -> Result<Self, BuildError> {
+        let Some(section) = self.config.sections.last_mut() else {
+            return Err(BuildError::NoSection { key: key.to_string() });
+        };
+        if !section.set(key, value) || section.properties.is_nonstandard(&key.to_ascii_lowercase())
+        {
+            return Err(BuildError::InvalidValue {
+                key: key.to_string(),
+                value: value.to_string(),
+            });
+        }
+        Ok(self)
+    }
+
+    pub fn build(mut self) -> EditorConfig {
+        // Clones of the builder share the cache, but may have added different sections since.
+        self.config.merge_cache = MergeCache::default();
+        self.config.extension_index = OnceLock::new();
+        self.config
+    }
+}
+
+/// Builds [EditorConfigProperties] programmatically, see [EditorConfigProperties::builder].
+#[derive(Debug, Default, Clone)]
+pub struct EditorConfigPropertiesBuilder {
+    properties: EditorConfigProperties,
+}
+
+impl EditorConfigProperties {
+    /// Create a builder for constructing properties programmatically, e.g.
+    /// `EditorConfigProperties::builder().indent_style(IndentStyle::Space).indent_size(2).build()`.
+    pub fn builder() -> EditorConfigPropertiesBuilder {
+        EditorConfigPropertiesBuilder::default()
+    }
+}
+
+impl EditorConfigPropertiesBuilder {
+    pub fn indent_style(mut self, indent_style: IndentStyle) -> Self {
+        self.properties.indent_style = EditorConfigProperty::Value(indent_style);
+        self
+    }
+
+    pub fn indent_size(mut self, indent_size: usize) -> Self {
+        self.properties.indent_size = EditorConfigProperty::Value(IndentSize::Number(indent_size));
+        self
+    }
+
+    /// `indent_size = tab`, see [IndentSize::Tab].
+    pub fn indent_size_tab(mut self) -> Self {
+        self.properties.indent_size = EditorConfigProperty::Value(IndentSize::Tab);
+        self
+    }
+
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.properties.tab_width = EditorConfigProperty::Value(tab_width);
+        self
+    }
+
+    pub fn end_of_line(mut self, end_of_line: EndOfLine) -> Self {
+        self.properties.end_of_line = EditorConfigProperty::Value(end_of_line);
+        self
+    }
+
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.properties.charset = EditorConfigProperty::Value(charset);
+        self
+    }
+
+    pub fn trim_trailing_whitespace(mut self, trim_trailing_whitespace: bool) -> Self {
+        self.properties.trim_trailing_whitespace =
+            EditorConfigProperty::Value(trim_trailing_whitespace);
+        self
+    }
+
+    pub fn insert_final_newline(mut self, insert_final_newline: bool) -> Self {
+        self.properties.insert_final_newline = EditorConfigProperty::Value(insert_final_newline);
+        self
+    }
+
+    pub fn max_line_length(mut self, max_line_length: MaxLineLength) -> Self {
+        self.properties.max_line_length = EditorConfigProperty::Value(max_line_length);
+        self
+    }
+
+    /// Set `key` to `unset`, removing a value set by earlier sections when merged.
+    pub fn unset(mut self, key: PropertyKey) -> Self {
+        let properties = &mut self.properties;
+        match key {
+            PropertyKey::IndentStyle => properties.indent_style = EditorConfigProperty::Unset,
+            PropertyKey::IndentSize => properties.indent_size = EditorConfigProperty::Unset,
+            PropertyKey::TabWidth => properties.tab_width = EditorConfigProperty::Unset,
+            PropertyKey::EndOfLine => properties.end_of_line = EditorConfigProperty::Unset,
+            PropertyKey::Charset => properties.charset = EditorConfigProperty::Unset,
+            PropertyKey::TrimTrailingWhitespace => {
+                properties.trim_trailing_whitespace = EditorConfigProperty::Unset;
+            }
+            PropertyKey::InsertFinalNewline => {
+                properties.insert_final_newline = EditorConfigProperty::Unset;
+            }
+            PropertyKey::MaxLineLength => properties.max_line_length = EditorConfigProperty::Unset,
+        }
+        self
+    }
+
+    pub fn build(self) -> EditorConfigProperties {
+        self.properties
+    }
+}
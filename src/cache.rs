@@ -0,0 +1,44 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::EditorConfigProperties;
+
+/// At most this many sets of matching sections are cached, so that configs resolving a huge
+/// number of distinct combinations do not grow without bound.
+const MAX_ENTRIES: usize = 1024;
+
+/// Merged properties per set of matching sections, since many paths (e.g. every `*.ts` file)
+/// match the same sections.
+///
+/// Shared by clones of an [EditorConfig](crate::EditorConfig): section properties never change
+/// once a config is parsed or built, so the merged properties of a set of section indices stay
+/// valid. [EditorConfigBuilder::build](crate::EditorConfigBuilder::build) starts a fresh cache,
+/// as clones of a builder can go on to add different properties.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MergeCache {
+    merged: Arc<RwLock<HashMap<Box<[usize]>, EditorConfigProperties>>>,
+}
+
+impl MergeCache {
+    /// The merged properties of the sections at `matched`, merged by `merge` if not cached,
+    /// and whether they were cached.
+    pub(crate) fn get_or_merge<F: FnOnce() -> EditorConfigProperties>(
+        &self,
+        matched: &[usize],
+        merge: F,
+    ) -> (EditorConfigProperties, bool) {
+        if let Some(properties) =
+            self.merged.read().unwrap_or_else(PoisonError::into_inner).get(matched)
+        {
+            return (properties.clone(), true);
+        }
+        let properties = merge();
+        let mut merged = self.merged.write().unwrap_or_else(PoisonError::into_inner);
+        if merged.len() < MAX_ENTRIES {
+            merged.insert(matched.into(), properties.clone());
+        }
+        (properties, false)
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    fmt, io,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{EditorConfig, EditorConfigProperties};
+
+/// A batch operation stopped because its cancellation flag was set.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// For batch operations returning [io::Result], as an [io::ErrorKind::Interrupted] error.
+impl From<Cancelled> for io::Error {
+    fn from(cancelled: Cancelled) -> Self {
+        Self::new(io::ErrorKind::Interrupted, cancelled)
+    }
+}
+
+/// Fail if `cancel` is set.
+pub(crate) fn check(cancel: &AtomicBool) -> Result<(), Cancelled> {
+    if cancel.load(Ordering::Relaxed) { Err(Cancelled) } else { Ok(()) }
+}
+
+impl EditorConfig {
+    /// Resolve every path in order, like [EditorConfig::resolve].
+    ///
+    /// `cancel` is checked before each path, so setting it from another thread, e.g. on a
+    /// keystroke, abandons the rest of the batch promptly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Cancelled] if `cancel` is set before the last path is resolved.
+    pub fn resolve_all<I, P>(
+        &self,
+        paths: I,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<EditorConfigProperties>, Cancelled>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        paths
+            .into_iter()
+            .map(|path| {
+                check(cancel)?;
+                Ok(self.resolve(path.as_ref()))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    EditorConfig,
+    diagnostic::Message,
+    glob::{Candidate, GlobInterner},
+};
+
+/// A path whose matching sections depend on letter case.
+///
+/// On a case-insensitive file system, `Foo.TS` and `foo.ts` are the same file,
+/// but case-sensitive globs such as `[*.ts]` only match one of them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CaseMismatch {
+    pub path: PathBuf,
+
+    /// Names of the sections that match the path only when ignoring case.
+    pub sections: Vec<String>,
+}
+
+impl Message for CaseMismatch {
+    fn id(&self) -> &'static str {
+        "case-mismatch"
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        let sections =
+            self.sections.iter().map(|name| format!("[{name}]")).collect::<Vec<_>>().join(", ");
+        vec![("path", self.path.display().to_string()), ("sections", sections)]
+    }
+}
+
+impl EditorConfig {
+    /// Detect sections that would match `path` on a case-insensitive file system but do not
+    /// match it as written, which explains surprising results for e.g. `Foo.TS`.
+    ///
+    /// Returns `None` when there is no such section or case-insensitive matching is enabled.
+    pub fn case_mismatch(&self, path: &Path) -> Option<CaseMismatch> {
+        if self.case_insensitive {
+            return None;
+        }
+        let folded_matchers = self.case_folded_matchers.get_or_init(|| {
+            let mut globs = GlobInterner::default();
+            let mut compile =
+                |name: &str| globs.compile(name, true, self.glob_limits, self.match_options).ok();
+            self.sections.iter().map(|section| compile(&section.name)).collect()
+        });
+        let relative = self.relative_path(path)?;
+        let candidate = Candidate::from_bytes(&relative);
+        let sections = self
+            .sections
+            .iter()
+            .zip(folded_matchers)
+            .filter(|(section, folded)| {
+                !section
+                    .matcher
+                    .as_ref()
+                    .is_some_and(|matcher| matcher.is_match_candidate(&candidate))
+                    && folded.as_ref().is_some_and(|matcher| matcher.is_match_candidate(&candidate))
+            })
+            .map(|(section, _)| section.name.clone())
+            .collect::<Vec<_>>();
+        (!sections.is_empty()).then(|| CaseMismatch { path: path.to_path_buf(), sections })
+    }
+}
@@ -0,0 +1,129 @@
+//! Aggregate statistics over a corpus of real-world `.editorconfig` files,
+//! to find out which properties, glob features and failure modes matter in practice.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use crate::{Diagnostic, DiagnosticKind, EditorConfig, PatternKind, cancel, detect, glob};
+
+/// A glob syntax feature used by section names.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum GlobFeature {
+    /// A `/`, anchoring the pattern to the config file's directory.
+    Path,
+    /// `*`.
+    Star,
+    /// `**`.
+    DoubleStar,
+    /// `?`.
+    AnyChar,
+    /// `[name]` or `[!name]`.
+    Class,
+    /// `{s1,s2,s3}`.
+    Alternation,
+    /// `{num1..num2}`.
+    NumericRange,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CorpusStats {
+    /// The number of files parsed.
+    pub files: usize,
+
+    /// The number of sections across all files.
+    pub sections: usize,
+
+    /// Keys that are not supported, with the number of times each was seen.
+    pub unknown_keys: BTreeMap<String, usize>,
+
+    /// Supported keys whose value could not be parsed or is outside the specification,
+    /// with the number of times each was seen.
+    pub invalid_values: BTreeMap<String, usize>,
+
+    /// Glob features, with the number of sections using each.
+    pub glob_features: BTreeMap<GlobFeature, usize>,
+
+    /// Pattern kinds, with the number of sections of each.
+    pub pattern_kinds: BTreeMap<PatternKind, usize>,
+
+    /// Section globs that failed to compile, as reported by [EditorConfig::parse_with_diagnostics],
+    /// with the file they occur in. Invalid values are counted in `invalid_values`.
+    pub parse_failures: Vec<(PathBuf, Diagnostic)>,
+}
+
+/// Parse every file named `.editorconfig`, or ending with `.editorconfig`, under `dir`.
+/// Symbolic links are not followed.
+///
+/// # Errors
+///
+/// Returns an error if a directory or file cannot be read.
+pub fn ingest<P: AsRef<Path>>(dir: P) -> io::Result<CorpusStats> {
+    ingest_with_cancel(dir, &AtomicBool::new(false))
+}
+
+/// Parse files like [ingest], checking `cancel` before reading each directory and file.
+///
+/// # Errors
+///
+/// Returns an error if a directory or file cannot be read, or an [io::ErrorKind::Interrupted]
+/// error wrapping [Cancelled](crate::Cancelled) if `cancel` is set.
+pub fn ingest_with_cancel<P: AsRef<Path>>(dir: P, cancel: &AtomicBool) -> io::Result<CorpusStats> {
+    let mut stats = CorpusStats::default();
+    let mut pending = vec![dir.as_ref().to_path_buf()];
+    let mut files = vec![];
+    while let Some(dir) = pending.pop() {
+        cancel::check(cancel)?;
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if !file_type.is_symlink()
+                && entry.file_name().as_encoded_bytes().ends_with(b".editorconfig")
+            {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    for file in files {
+        cancel::check(cancel)?;
+        stats.add(&file, &fs::read(&file)?);
+    }
+    Ok(stats)
+}
+
+impl CorpusStats {
+    /// Add the statistics of one file, decoded like [EditorConfig::parse_bytes].
+    pub fn add(&mut self, path: &Path, source: &[u8]) {
+        let (editor_config, diagnostics) =
+            EditorConfig::parse_with_diagnostics(&detect::decode(source));
+        self.files += 1;
+        self.sections += editor_config.sections().len();
+        for section in editor_config.sections() {
+            for feature in glob::features(&section.name) {
+                *self.glob_features.entry(feature).or_default() += 1;
+            }
+            *self.pattern_kinds.entry(section.pattern_kind()).or_default() += 1;
+            for (key, _) in &section.unknown_properties {
+                *self.unknown_keys.entry(key.clone()).or_default() += 1;
+            }
+        }
+        for diagnostic in diagnostics {
+            match diagnostic.kind {
+                DiagnosticKind::InvalidValue { key, .. }
+                | DiagnosticKind::NonstandardValue { key, .. } => {
+                    *self.invalid_values.entry(key).or_default() += 1;
+                }
+                DiagnosticKind::InvalidGlob { .. } => {
+                    self.parse_failures.push((path.to_path_buf(), diagnostic));
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Detection of the properties of existing files, for comparing them with the configured ones.
+
+use std::borrow::Cow;
+
+use crate::Charset;
+
+/// Detect the charset of a file's contents from its byte order mark, or else by checking
+/// whether it is valid UTF-8 or plausibly latin1.
+///
+/// Text without a byte order mark that is valid UTF-8, including plain ASCII, is `utf-8`.
+/// Returns `None` when unsure, e.g. for binary contents or UTF-16 without a byte order mark.
+pub fn charset(bytes: &[u8]) -> Option<Charset> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some(Charset::Utf8bom);
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        return Some(Charset::Utf16be);
+    }
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return Some(Charset::Utf16le);
+    }
+    // NUL is not expected in text, but is in UTF-16 text without a byte order mark.
+    if bytes.contains(&0) {
+        return None;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return Some(Charset::Utf8);
+    }
+    // C1 control characters are rare in latin1 text, but common in other 8-bit encodings.
+    (!bytes.iter().any(|b| (0x80..=0x9F).contains(b))).then_some(Charset::Latin1)
+}
+
+/// Decode text in the charset [charset] detects, without its byte order mark.
+/// Invalid sequences are replaced with U+FFFD, as are contents of unknown charset.
+pub(crate) fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    let utf16 = |bytes: &[u8], unit: fn([u8; 2]) -> u16| {
+        let units = bytes.chunks(2).map(|pair| unit([pair[0], pair.get(1).copied().unwrap_or(0)]));
+        char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect()
+    };
+    match charset(bytes) {
+        Some(Charset::Utf8bom) => String::from_utf8_lossy(&bytes[3..]),
+        Some(Charset::Utf16be) => Cow::Owned(utf16(&bytes[2..], u16::from_be_bytes)),
+        Some(Charset::Utf16le) => Cow::Owned(utf16(&bytes[2..], u16::from_le_bytes)),
+        Some(Charset::Latin1) => Cow::Owned(bytes.iter().map(|b| char::from(*b)).collect()),
+        Some(Charset::Utf8 | Charset::Other(_)) | None => String::from_utf8_lossy(bytes),
+    }
+}
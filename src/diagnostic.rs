@@ -0,0 +1,324 @@
+//! Diagnostic messages as stable identifiers plus data.
+//!
+//! Messages are rendered from templates such as `invalid value `{value}` for `{key}``,
+//! so downstream tools can translate or re-render them by providing their own catalog
+//! instead of parsing preformatted strings.
+//!
+//! Every message also has a stable code (e.g. `EC002`) with extended documentation
+//! available from [explain].
+
+use std::fmt;
+
+/// A problem found while parsing.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line number.
+    pub line: usize,
+
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    /// The section header's glob failed to compile, so the section never matches.
+    InvalidGlob { pattern: String, reason: String },
+    /// The value of a known property is invalid, so the property is ignored.
+    InvalidValue { key: String, value: String },
+    /// The value of a known property is outside the specification, and kept as `Other`,
+    /// e.g. [EndOfLine::Other](crate::EndOfLine::Other).
+    NonstandardValue { key: String, value: String },
+    /// A known property before the first section, where it has no effect.
+    NoSection { key: String },
+    /// A line starting with `[` without a closing `]`, which is ignored.
+    InvalidHeader { header: String },
+    /// A section with the same name as an earlier one, on `first_line`.
+    DuplicateSection { name: String, first_line: usize },
+    /// A key set again in the same section, overriding the pair on `previous_line`.
+    DuplicateKey { key: String, previous_line: usize },
+    /// The source text is longer than [InputLimits::max_input_len](crate::InputLimits), so this
+    /// line and the rest are ignored.
+    InputTooLong { max: usize },
+    /// A section header over [InputLimits::max_sections](crate::InputLimits), which is ignored
+    /// with the rest of the source text.
+    TooManySections { max: usize },
+    /// A line longer than [InputLimits::max_line_len](crate::InputLimits), which is ignored.
+    LineTooLong { max: usize },
+}
+
+impl Message for Diagnostic {
+    fn id(&self) -> &'static str {
+        match self.kind {
+            DiagnosticKind::InvalidGlob { .. } => "invalid-glob",
+            DiagnosticKind::InvalidValue { .. } => "invalid-value",
+            DiagnosticKind::NonstandardValue { .. } => "nonstandard-value",
+            DiagnosticKind::NoSection { .. } => "no-section",
+            DiagnosticKind::InvalidHeader { .. } => "invalid-header",
+            DiagnosticKind::DuplicateSection { .. } => "duplicate-section",
+            DiagnosticKind::DuplicateKey { .. } => "duplicate-key",
+            DiagnosticKind::InputTooLong { .. } => "input-too-long",
+            DiagnosticKind::TooManySections { .. } => "too-many-sections",
+            DiagnosticKind::LineTooLong { .. } => "line-too-long",
+        }
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match &self.kind {
+            DiagnosticKind::InvalidGlob { pattern, reason } => {
+                vec![("pattern", pattern.clone()), ("reason", reason.clone())]
+            }
+            DiagnosticKind::InvalidValue { key, value }
+            | DiagnosticKind::NonstandardValue { key, value } => {
+                vec![("key", key.clone()), ("value", value.clone())]
+            }
+            DiagnosticKind::NoSection { key } => vec![("key", key.clone())],
+            DiagnosticKind::InvalidHeader { header } => vec![("header", header.clone())],
+            DiagnosticKind::DuplicateSection { name, first_line } => {
+                vec![("name", name.clone()), ("first_line", first_line.to_string())]
+            }
+            DiagnosticKind::DuplicateKey { key, previous_line } => {
+                vec![("key", key.clone()), ("previous_line", previous_line.to_string())]
+            }
+            DiagnosticKind::InputTooLong { max }
+            | DiagnosticKind::TooManySections { max }
+            | DiagnosticKind::LineTooLong { max } => vec![("max", max.to_string())],
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, render(self, english))
+    }
+}
+
+/// A problem that makes [crate::EditorConfig::try_parse] fail.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+
+    /// 1-based column of `text`, in characters.
+    pub column: usize,
+
+    /// The offending text: the header, value or glob.
+    pub text: String,
+
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// A line starting with `[` without a closing `]`.
+    InvalidHeader,
+
+    /// The value of a known property could not be parsed.
+    InvalidValue { key: String },
+
+    /// The section header's glob failed to compile.
+    InvalidGlob { reason: String },
+}
+
+impl Message for ParseError {
+    fn id(&self) -> &'static str {
+        match self.kind {
+            ParseErrorKind::InvalidHeader => "invalid-header",
+            ParseErrorKind::InvalidValue { .. } => "invalid-value",
+            ParseErrorKind::InvalidGlob { .. } => "invalid-glob",
+        }
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match &self.kind {
+            ParseErrorKind::InvalidHeader => vec![("header", self.text.clone())],
+            ParseErrorKind::InvalidValue { key } => {
+                vec![("key", key.clone()), ("value", self.text.clone())]
+            }
+            ParseErrorKind::InvalidGlob { reason } => {
+                vec![("pattern", self.text.clone()), ("reason", reason.clone())]
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, render(self, english))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A diagnostic message.
+pub trait Message {
+    /// Stable identifier of the message, e.g. `invalid-glob`.
+    fn id(&self) -> &'static str;
+
+    /// Named arguments referenced as `{name}` by the message template.
+    fn args(&self) -> Vec<(&'static str, String)>;
+
+    /// Stable code of the message, e.g. `EC001`.
+    fn code(&self) -> Option<&'static str> {
+        code(self.id())
+    }
+}
+
+struct Entry {
+    /// Codes are never reused or renumbered.
+    code: &'static str,
+    id: &'static str,
+    template: &'static str,
+    explanation: &'static str,
+}
+
+const CATALOG: &[Entry] = &[
+    Entry {
+        code: "EC001",
+        id: "invalid-glob",
+        template: "invalid glob `{pattern}`: {reason}",
+        explanation: "\
+The section name could not be compiled into a glob, so the section never matches any file.
+
+Check character classes such as `[z-a]`, whose range must be in ascending order.",
+    },
+    Entry {
+        code: "EC002",
+        id: "invalid-value",
+        template: "invalid value `{value}` for `{key}`",
+        explanation: "\
+The value is not valid for a known property, so the property is treated as not specified.
+
+Values are case-insensitive. Every property accepts `unset` to remove a previously set value.",
+    },
+    Entry {
+        code: "EC003",
+        id: "no-section",
+        template: "property `{key}` must be inside a section",
+        explanation: "\
+Properties before the first section header are ignored, since they apply to no files; only
+`root` is recognized there. Add a section header such as `[*]` before the property.",
+    },
+    Entry {
+        code: "EC004",
+        id: "case-mismatch",
+        template: "`{path}` only matches {sections} when ignoring case",
+        explanation: "\
+Section globs are case-sensitive, so on a case-insensitive file system `Foo.TS` and `foo.ts`
+name the same file but `[*.ts]` only applies to one of them.
+
+Use a class such as `[*.[tT][sS]]`, or enable case-insensitive matching.",
+    },
+    Entry {
+        code: "EC005",
+        id: "outside-cwd",
+        template: "`{path}` is outside `{cwd}`",
+        explanation: "\
+The path could not be made relative to the working directory that section globs are matched in,
+so sections such as `[src/**]` could apply to it by accident.
+
+Resolve paths under the working directory, or choose how to match other paths with
+`ResolveOptions`.",
+    },
+    Entry {
+        code: "EC006",
+        id: "invalid-header",
+        template: "invalid section header `{header}`",
+        explanation: "\
+A line starting with `[` is a section header and must have a closing `]`. The line is ignored,
+so the properties below it still belong to the previous section.",
+    },
+    Entry {
+        code: "EC007",
+        id: "duplicate-section",
+        template: "section `[{name}]` repeats the section on line {first_line}",
+        explanation: "\
+Both sections apply, in order, so pairs in the later one override the earlier one. Move the
+pairs into one section, or set `ParseOptions::merge_duplicate_sections` to merge them.",
+    },
+    Entry {
+        code: "EC008",
+        id: "duplicate-key",
+        template: "`{key}` overrides the value on line {previous_line}",
+        explanation: "\
+The key is set more than once in the same section, and only the last value is used.
+Keys are case-insensitive. Remove the earlier pairs.",
+    },
+    Entry {
+        code: "EC009",
+        id: "input-too-long",
+        template: "the file is longer than {max} bytes, the rest is ignored",
+        explanation: "\
+The file exceeds `InputLimits::max_input_len`, so parsing stopped at the line crossing the limit.",
+    },
+    Entry {
+        code: "EC010",
+        id: "too-many-sections",
+        template: "more than {max} sections, the rest is ignored",
+        explanation: "\
+The file exceeds `InputLimits::max_sections`, so parsing stopped at the first section header
+over the limit.",
+    },
+    Entry {
+        code: "EC011",
+        id: "line-too-long",
+        template: "line longer than {max} bytes is ignored",
+        explanation: "\
+The line exceeds `InputLimits::max_line_len`, so it is ignored, as if it were a comment.",
+    },
+    Entry {
+        code: "EC012",
+        id: "nonstandard-value",
+        template: "nonstandard value `{value}` for `{key}`",
+        explanation: "\
+The value is not one the specification defines for the property, such as `end_of_line = native`.
+It is kept as `Other` for tools that support it; others treat the property as not specified.
+Check for typos such as `indent_style = tabs`. `try_parse` fails on such values.",
+    },
+];
+
+/// The default English template for a message id.
+pub fn english(id: &str) -> Option<&'static str> {
+    CATALOG.iter().find(|entry| entry.id == id).map(|entry| entry.template)
+}
+
+/// The stable code for a message id.
+pub fn code(id: &str) -> Option<&'static str> {
+    CATALOG.iter().find(|entry| entry.id == id).map(|entry| entry.code)
+}
+
+/// Extended documentation for a code (e.g. `EC001`) or message id (e.g. `invalid-glob`).
+pub fn explain(code_or_id: &str) -> Option<&'static str> {
+    CATALOG
+        .iter()
+        .find(|entry| entry.code.eq_ignore_ascii_case(code_or_id) || entry.id == code_or_id)
+        .map(|entry| entry.explanation)
+}
+
+/// Render a message with templates from `catalog`, falling back to [english].
+pub fn render<'a, F>(message: &dyn Message, catalog: F) -> String
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let id = message.id();
+    let template = catalog(id).or_else(|| english(id)).unwrap_or(id);
+    let args = message.args();
+    // Substitute in one pass over the template, so `{name}` inside a value is kept as is.
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..end])?;
+            Some((value, end))
+        });
+        if let Some((value, end)) = value {
+            text.push_str(value);
+            rest = &rest[end + 1..];
+        } else {
+            text.push('{');
+            rest = &rest[1..];
+        }
+    }
+    text.push_str(rest);
+    text
+}
@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use crate::{EditorConfig, EditorConfigProperties, EditorConfigSection, Resolved, glob};
+
+/// The sections of an [EditorConfig] that may apply to files under one directory,
+/// for resolving many files in it without matching every section against each of them.
+#[derive(Debug, Clone)]
+pub struct DirectoryConfig<'a> {
+    config: &'a EditorConfig,
+    sections: Vec<&'a EditorConfigSection>,
+    /// The indices of `sections` in the config, in order.
+    indices: Vec<usize>,
+}
+
+impl EditorConfig {
+    /// Pre-filter the sections that may apply to files under `dir`, e.g. `[src/**.rs]` is
+    /// skipped for `docs`. `dir` is made relative like paths passed to [EditorConfig::resolve].
+    ///
+    /// Files outside `dir` must not be resolved with the result.
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn for_directory(&self, dir: &Path) -> DirectoryConfig<'_> {
+        let indices: Vec<usize> = match self.relative_path(dir) {
+            Some(dir) => (0..self.sections.len())
+                .filter(|i| {
+                    let section = &self.sections[*i];
+                    section.matcher.is_some()
+                        && (self.case_insensitive
+                            || glob::may_match_under(&section.name, &dir, self.match_options))
+                })
+                .collect(),
+            None => vec![],
+        };
+        let sections = indices.iter().map(|i| &self.sections[*i]).collect();
+        DirectoryConfig { config: self, sections, indices }
+    }
+}
+
+impl<'a> DirectoryConfig<'a> {
+    /// The sections that may apply to files under the directory, in order.
+    pub fn sections(&self) -> &[&'a EditorConfigSection] {
+        &self.sections
+    }
+
+    /// Resolve a file under the directory like [EditorConfig::resolve].
+    pub fn resolve(&self, path: &Path) -> EditorConfigProperties {
+        self.resolve_with_matches(path).properties
+    }
+
+    /// Resolve a file under the directory like [EditorConfig::resolve_with_matches].
+    pub fn resolve_with_matches(&self, path: &Path) -> Resolved {
+        self.config
+            .relative_path(path)
+            .map(|path| {
+                self.config.resolve_sections(&path, |i| self.indices.binary_search(&i).is_ok())
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use crate::EditorConfig;
+
+/// An `.editorconfig` file for editing, which keeps comments, blank lines, whitespace and the
+/// order of properties, and renders back to exactly the original text when unchanged.
+///
+/// Comment lines directly above a section header or a pair, without a blank line in between,
+/// are attached to it: they are removed and moved along with a pair.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct EditorConfigDocument {
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Line {
+    /// The line without its line ending.
+    text: String,
+    /// `\n`, `\r\n`, or empty for a last line without one.
+    ending: String,
+}
+
+enum Kind<'a> {
+    Header(&'a str),
+    Pair { key: &'a str, value: &'a str },
+    Other,
+}
+
+impl Line {
+    fn new(text: String) -> Self {
+        Self { text, ending: "\n".to_string() }
+    }
+
+    fn is_comment(&self) -> bool {
+        self.text.trim_start().starts_with([';', '#'])
+    }
+
+    /// Whether this is a pair of `key`, which is case-insensitive.
+    fn has_key(&self, key: &str) -> bool {
+        matches!(self.kind(), Kind::Pair { key: k, .. } if k.eq_ignore_ascii_case(key))
+    }
+
+    fn kind(&self) -> Kind<'_> {
+        let line = self.text.trim();
+        if line.starts_with([';', '#']) {
+            return Kind::Other;
+        }
+        if line.starts_with('[') {
+            return crate::parser::section_name(line).map_or(Kind::Other, Kind::Header);
+        }
+        match line.split_once('=') {
+            Some((key, value)) => Kind::Pair { key: key.trim_end(), value: value.trim_start() },
+            None => Kind::Other,
+        }
+    }
+}
+
+impl EditorConfigDocument {
+    pub fn parse(source_text: &str) -> Self {
+        let lines = crate::parser::lines_inclusive(source_text)
+            .map(|line| {
+                let text = line.strip_suffix('\n').unwrap_or(line);
+                let text = text.strip_suffix('\r').unwrap_or(text);
+                Line { text: text.to_string(), ending: line[text.len()..].to_string() }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// Parse the document as an [EditorConfig].
+    pub fn to_config(&self) -> EditorConfig {
+        EditorConfig::parse(&self.to_string())
+    }
+
+    /// The value of `key`, which is case-insensitive, in the last section named `section`.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        let i = self.find_pair(section, key)?;
+        match self.lines[i].kind() {
+            Kind::Pair { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Set `key` in the last section named `section`, keeping the whitespace around an existing
+    /// value. A new pair is added after the last pair of the section, and a missing section is
+    /// added at the end of the document.
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        if let Some(i) = self.find_pair(section, key) {
+            let line = &mut self.lines[i];
+            let Kind::Pair { value: old, .. } = line.kind() else { unreachable!() };
+            let start = crate::offset(&line.text, old);
+            line.text.replace_range(start..start + old.len(), value);
+            return;
+        }
+        let pair = Line::new(format!("{key} = {value}"));
+        if let Some(range) = self.find_section(section) {
+            let at = self.after_pairs(range);
+            if at == self.lines.len() {
+                self.end_last_line();
+            }
+            self.lines.insert(at, pair);
+            return;
+        }
+        self.end_last_line();
+        if !self.lines.is_empty() {
+            self.lines.push(Line::new(String::new()));
+        }
+        self.lines.push(Line::new(format!("[{section}]")));
+        self.lines.push(pair);
+    }
+
+    /// Remove every `key` in the last section named `section`, with their attached comments.
+    /// Returns whether there was one.
+    pub fn remove(&mut self, section: &str, key: &str) -> bool {
+        let Some(range) = self.find_section(section) else {
+            return false;
+        };
+        let removed: Vec<_> =
+            range.filter(|i| self.lines[*i].has_key(key)).map(|i| self.attached(i)).collect();
+        let mut i = 0;
+        self.lines.retain(|_| {
+            let keep = !removed.iter().any(|range| range.contains(&i));
+            i += 1;
+            keep
+        });
+        !removed.is_empty()
+    }
+
+    /// The comment lines attached to the last section named `section` if `key` is `None`,
+    /// or else to the pair of `key` in it, e.g. `# 4-wide because of legacy code`.
+    pub fn comments(&self, section: &str, key: Option<&str>) -> Vec<&str> {
+        let i = match key {
+            Some(key) => self.find_pair(section, key),
+            None => self.find_section(section).map(|range| range.start - 1),
+        };
+        i.map_or_else(Vec::new, |i| {
+            self.lines[self.attached(i).start..i].iter().map(|line| line.text.trim()).collect()
+        })
+    }
+
+    /// Move the pair of `key` in the last section named `section`, with its attached comments,
+    /// above the pair of `before`, or after the last pair of the section if `before` is `None`.
+    /// Returns whether both pairs were found.
+    pub fn move_pair(&mut self, section: &str, key: &str, before: Option<&str>) -> bool {
+        let Some(i) = self.find_pair(section, key) else {
+            return false;
+        };
+        if let Some(before) = before {
+            if self.find_pair(section, before).is_none() {
+                return false;
+            }
+            if before.eq_ignore_ascii_case(key) {
+                return true;
+            }
+        }
+        let mut moved: Vec<Line> = self.lines.drain(self.attached(i)).collect();
+        for line in &mut moved {
+            if line.ending.is_empty() {
+                line.ending = "\n".to_string();
+            }
+        }
+        let at = match before {
+            Some(before) => self.find_pair(section, before).map(|j| self.attached(j).start),
+            None => self.find_section(section).map(|range| self.after_pairs(range)),
+        };
+        let at = at.unwrap_or(self.lines.len());
+        if at == self.lines.len() {
+            self.end_last_line();
+        }
+        self.lines.splice(at..at, moved);
+        true
+    }
+
+    /// The line after the last pair of a section, given the lines after its header.
+    fn after_pairs(&self, range: std::ops::Range<usize>) -> usize {
+        let start = range.start;
+        range
+            .rev()
+            .find(|i| matches!(self.lines[*i].kind(), Kind::Pair { .. }))
+            .unwrap_or(start - 1)
+            + 1
+    }
+
+    /// The lines of the comments attached to line `i`, and line `i`.
+    fn attached(&self, i: usize) -> std::ops::Range<usize> {
+        let start =
+            self.lines[..i].iter().rposition(|line| !line.is_comment()).map_or(0, |j| j + 1);
+        start..i + 1
+    }
+
+    /// The lines after the header of the last section named `section`.
+    pub(crate) fn find_section(&self, section: &str) -> Option<std::ops::Range<usize>> {
+        let start = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line.kind(), Kind::Header(name) if name == section))?
+            + 1;
+        let end = self.lines[start..]
+            .iter()
+            .position(|line| matches!(line.kind(), Kind::Header(_)))
+            .map_or(self.lines.len(), |len| start + len);
+        Some(start..end)
+    }
+
+    pub(crate) fn find_pair(&self, section: &str, key: &str) -> Option<usize> {
+        self.find_section(section)?.rev().find(|i| self.lines[*i].has_key(key))
+    }
+
+    fn end_last_line(&mut self) {
+        if let Some(line) = self.lines.last_mut()
+            && line.ending.is_empty()
+        {
+            line.ending = "\n".to_string();
+        }
+    }
+}
+
+impl fmt::Display for EditorConfigDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            write!(f, "{}{}", line.text, line.ending)?;
+        }
+        Ok(())
+    }
+}
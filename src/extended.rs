@@ -0,0 +1,110 @@
+//! Widely used properties outside the specification, see [EditorConfigProperties::extended].
+//!
+//! <https://github.com/editorconfig/editorconfig/wiki/EditorConfig-Properties#ideas-for-domain-specific-properties>
+
+use crate::{EditorConfigProperties, EditorConfigProperty};
+
+/// Properties outside the specification, parsed from [EditorConfigProperties::unknown].
+/// The values are case-insensitive.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ExtendedProperties {
+    /// The preferred quotes for string literals.
+    pub quote_type: EditorConfigProperty<QuoteType>,
+
+    /// Whether to put the opening curly bracket of a block on the next line.
+    pub curly_bracket_next_line: EditorConfigProperty<bool>,
+
+    /// Whether to put spaces around operators.
+    pub spaces_around_operators: EditorConfigProperty<SpacesAroundOperators>,
+
+    /// Where to put spaces next to brackets.
+    pub spaces_around_brackets: EditorConfigProperty<SpacesAroundBrackets>,
+
+    /// The number of columns to indent continuation lines with.
+    pub continuation_indent_size: EditorConfigProperty<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum QuoteType {
+    Single,
+    Double,
+    /// Either, e.g. whichever needs fewer escapes.
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SpacesAroundOperators {
+    True,
+    False,
+    /// Only around operators with lower precedence than their neighbours.
+    Hybrid,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SpacesAroundBrackets {
+    None,
+    Inside,
+    Outside,
+    Both,
+}
+
+impl EditorConfigProperties {
+    /// The properties outside the specification in [ExtendedProperties], which are otherwise
+    /// only available as strings with [EditorConfigProperties::get_raw].
+    /// With the `spec-strict` feature, every property is unspecified.
+    pub fn extended(&self) -> ExtendedProperties {
+        if cfg!(feature = "spec-strict") {
+            return ExtendedProperties::default();
+        }
+        ExtendedProperties {
+            quote_type: self.parse_extended("quote_type", |value| match value {
+                "single" => Some(QuoteType::Single),
+                "double" => Some(QuoteType::Double),
+                "auto" => Some(QuoteType::Auto),
+                _ => None,
+            }),
+            curly_bracket_next_line: self.parse_extended("curly_bracket_next_line", |value| {
+                match value {
+                    "true" => Some(true),
+                    "false" => Some(false),
+                    _ => None,
+                }
+            }),
+            spaces_around_operators: self.parse_extended("spaces_around_operators", |value| {
+                match value {
+                    "true" => Some(SpacesAroundOperators::True),
+                    "false" => Some(SpacesAroundOperators::False),
+                    "hybrid" => Some(SpacesAroundOperators::Hybrid),
+                    _ => None,
+                }
+            }),
+            spaces_around_brackets: self.parse_extended("spaces_around_brackets", |value| {
+                match value {
+                    "none" => Some(SpacesAroundBrackets::None),
+                    "inside" => Some(SpacesAroundBrackets::Inside),
+                    "outside" => Some(SpacesAroundBrackets::Outside),
+                    "both" => Some(SpacesAroundBrackets::Both),
+                    _ => None,
+                }
+            }),
+            continuation_indent_size: self
+                .parse_extended("continuation_indent_size", |value| value.parse().ok()),
+        }
+    }
+
+    /// Parse the lowercased value of `key`, with `unset` as [EditorConfigProperty::Unset].
+    fn parse_extended<T>(
+        &self,
+        key: &str,
+        parse: fn(&str) -> Option<T>,
+    ) -> EditorConfigProperty<T> {
+        let Some(value) = self.get_raw(key) else {
+            return EditorConfigProperty::None;
+        };
+        let value = value.to_ascii_lowercase();
+        if value == "unset" {
+            return EditorConfigProperty::Unset;
+        }
+        parse(&value).map_or(EditorConfigProperty::None, EditorConfigProperty::Value)
+    }
+}
@@ -0,0 +1,61 @@
+//! Typed access to properties outside the specification, for downstream tools with their own keys.
+
+use std::{any::Any, collections::HashMap};
+
+use crate::{EditorConfigProperties, EditorConfigProperty};
+
+/// Parsers for properties that are not fields of [EditorConfigProperties], e.g. a formatter's
+/// `print_width`.
+///
+/// The values resolve like those of any unknown property, see
+/// [EditorConfigProperties::unknown], and are parsed when read with [PropertyRegistry::get].
+#[derive(Debug, Default)]
+pub struct PropertyRegistry {
+    /// A [Parser] for each lowercased key.
+    parsers: HashMap<String, Box<dyn Any + Send + Sync>>,
+}
+
+type Parser<T> = Box<dyn Fn(&str) -> Option<T> + Send + Sync>;
+
+impl PropertyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `key`, which is case-insensitive, with the parser of its values, returning
+    /// `None` for an invalid value. The parser may capture its configuration, e.g. a maximum.
+    /// Registering a key again replaces its parser.
+    pub fn register<T, F>(&mut self, key: &str, parse: F) -> &mut Self
+    where
+        T: Any,
+        F: Fn(&str) -> Option<T> + Send + Sync + 'static,
+    {
+        let parse: Parser<T> = Box::new(parse);
+        self.parsers.insert(key.to_ascii_lowercase(), Box::new(parse));
+        self
+    }
+
+    /// The value of `key` in `properties`, parsed with the parser registered for it.
+    ///
+    /// [EditorConfigProperty::None] if the key is not set, its value is invalid, or it was not
+    /// registered with a parser returning `T`.
+    pub fn get<T: Any>(
+        &self,
+        properties: &EditorConfigProperties,
+        key: &str,
+    ) -> EditorConfigProperty<T> {
+        let key = key.to_ascii_lowercase();
+        let Some(parse) =
+            self.parsers.get(&key).and_then(|parse| parse.downcast_ref::<Parser<T>>())
+        else {
+            return EditorConfigProperty::None;
+        };
+        match properties.get_raw(&key) {
+            None => EditorConfigProperty::None,
+            Some(value) if value.eq_ignore_ascii_case("unset") => EditorConfigProperty::Unset,
+            Some(value) => {
+                parse(value).map_or(EditorConfigProperty::None, EditorConfigProperty::Value)
+            }
+        }
+    }
+}
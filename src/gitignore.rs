@@ -0,0 +1,50 @@
+use crate::EditorConfigSection;
+
+/// A gitignore idiom in a section pattern which does not carry over to EditorConfig.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GitignoreIsm {
+    pub kind: GitignoreIsmKind,
+
+    /// An EditorConfig glob matching the same files the pattern would match in a `.gitignore`,
+    /// if there is one.
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GitignoreIsmKind {
+    /// `!pattern` negates a pattern in gitignore, but is a literal `!` in EditorConfig.
+    Negation,
+    /// `dir/` matches a directory at any depth in gitignore,
+    /// but only the directory next to the `.editorconfig` in EditorConfig.
+    TrailingSlash,
+}
+
+/// Report gitignore idioms in an EditorConfig section pattern.
+pub fn gitignore_isms(pattern: &str) -> Vec<GitignoreIsm> {
+    let mut isms = vec![];
+    let negated = pattern.strip_prefix('!');
+    if negated.is_some() {
+        // EditorConfig has no negation, the closest is a later section setting `unset`.
+        isms.push(GitignoreIsm { kind: GitignoreIsmKind::Negation, suggestion: None });
+    }
+    let pattern = negated.unwrap_or(pattern);
+    // Without another `/`, gitignore matches the directory at any depth.
+    if let Some(dir) = pattern.strip_suffix('/')
+        && !dir.is_empty()
+        && !dir.ends_with('\\')
+        && !dir.contains('/')
+    {
+        isms.push(GitignoreIsm {
+            kind: GitignoreIsmKind::TrailingSlash,
+            suggestion: Some(format!("**/{dir}/**")),
+        });
+    }
+    isms
+}
+
+impl EditorConfigSection {
+    /// Report gitignore idioms in this section's pattern. See [gitignore_isms].
+    pub fn gitignore_isms(&self) -> Vec<GitignoreIsm> {
+        gitignore_isms(&self.name)
+    }
+}
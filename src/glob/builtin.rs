@@ -0,0 +1,179 @@
+//! The built-in backend: patterns are matched directly, following editorconfig-core.
+//! With the `globset` feature, it only matches patterns with numeric ranges.
+//!
+//! Paths are matched as bytes; a byte that is not part of a valid UTF-8 sequence is a character
+//! of its own, matched only by wildcards.
+
+use super::{Candidate, GlobErrorKind, GlobLimits, MatchOptions, Token, tokens};
+
+#[derive(Debug, Clone)]
+pub(super) struct Matcher {
+    tokens: Vec<Token>,
+    case_insensitive: bool,
+    literal_separator: bool,
+}
+
+impl Matcher {
+    pub(super) fn compile(
+        pattern: &str,
+        case_insensitive: bool,
+        limits: GlobLimits,
+        options: MatchOptions,
+    ) -> Result<Self, GlobErrorKind> {
+        let tokens = tokens(pattern, limits, options)?;
+        validate(&tokens)?;
+        let max = limits.max_expansions;
+        if expansions(&tokens, true, true) > max {
+            return Err(GlobErrorKind::TooManyExpansions { max });
+        }
+        Ok(Self { tokens, case_insensitive, literal_separator: options.literal_separator })
+    }
+
+    pub(super) fn is_match_candidate(&self, candidate: &Candidate<'_>) -> bool {
+        self.matches(&self.tokens, candidate.path, true)
+    }
+
+    /// `after_separator` is whether `path` starts a path component.
+    fn matches(&self, tokens: &[Token], path: &[u8], after_separator: bool) -> bool {
+        let Some((token, rest)) = tokens.split_first() else {
+            return path.is_empty();
+        };
+        match token {
+            Token::Literal(c) => next_char(path).is_some_and(|(p, len)| {
+                p.is_some_and(|p| self.eq(*c, p)) && self.matches(rest, &path[len..], *c == '/')
+            }),
+            Token::Any => next_char(path).is_some_and(|(p, len)| {
+                !self.is_separator(p) && self.matches(rest, &path[len..], false)
+            }),
+            Token::Class { negated, ranges } => next_char(path).is_some_and(|(p, len)| {
+                !self.is_separator(p)
+                    && p.is_some_and(|p| self.in_ranges(ranges, p)) != *negated
+                    && self.matches(rest, &path[len..], false)
+            }),
+            Token::Star => {
+                let mut i = 0;
+                loop {
+                    if self.matches(rest, &path[i..], i == 0 && after_separator) {
+                        return true;
+                    }
+                    match next_char(&path[i..]) {
+                        Some((p, len)) if !self.is_separator(p) => i += len,
+                        _ => return false,
+                    }
+                }
+            }
+            Token::DoubleStar => {
+                // `**/` also matches no directory at all.
+                if after_separator
+                    && rest.first() == Some(&Token::Literal('/'))
+                    && self.matches(&rest[1..], path, true)
+                {
+                    return true;
+                }
+                let mut i = 0;
+                loop {
+                    let at_separator = if i == 0 { after_separator } else { path[i - 1] == b'/' };
+                    if self.matches(rest, &path[i..], at_separator) {
+                        return true;
+                    }
+                    match next_char(&path[i..]) {
+                        Some((_, len)) => i += len,
+                        None => return false,
+                    }
+                }
+            }
+            Token::Alternation(branches) => branches.iter().any(|branch| {
+                self.matches(&[branch.as_slice(), rest].concat(), path, after_separator)
+            }),
+            Token::Range(start, end) => {
+                let sign = usize::from(path.first().is_some_and(|b| matches!(b, b'+' | b'-')));
+                let digits = path[sign..].iter().take_while(|b| b.is_ascii_digit()).count();
+                (1..=digits).rev().any(|len| {
+                    let number = std::str::from_utf8(&path[..sign + len])
+                        .ok()
+                        .and_then(|number| number.parse::<i64>().ok());
+                    number.is_some_and(|n| (*start.min(end)..=*start.max(end)).contains(&n))
+                        && self.matches(rest, &path[sign + len..], false)
+                })
+            }
+        }
+    }
+
+    /// Whether `c` is a `/` that wildcards do not match.
+    fn is_separator(&self, c: Option<char>) -> bool {
+        self.literal_separator && c == Some('/')
+    }
+
+    fn eq(&self, a: char, b: char) -> bool {
+        a == b || (self.case_insensitive && a.to_lowercase().eq(b.to_lowercase()))
+    }
+
+    fn in_ranges(&self, ranges: &[(char, char)], c: char) -> bool {
+        let contains = |c: char| ranges.iter().any(|(start, end)| (*start..=*end).contains(&c));
+        contains(c)
+            || (self.case_insensitive
+                && (c.to_lowercase().any(contains) || c.to_uppercase().any(contains)))
+    }
+}
+
+/// Decode the next character of `path` and its length in bytes,
+/// or `None` for the character of a byte that is not valid UTF-8.
+fn next_char(path: &[u8]) -> Option<(Option<char>, usize)> {
+    let len = match path.first()? {
+        0xF0.. => 4,
+        0xE0.. => 3,
+        0xC0.. => 2,
+        _ => 1,
+    };
+    let c = path.get(..len).and_then(|bytes| std::str::from_utf8(bytes).ok());
+    Some(c.map_or((None, 1), |c| (c.chars().next(), len)))
+}
+
+/// Reject classes with reversed ranges such as `[z-a]`, as the `globset` backend does.
+fn validate(tokens: &[Token]) -> Result<(), GlobErrorKind> {
+    for token in tokens {
+        match token {
+            Token::Class { ranges, .. } => {
+                if let Some((start, end)) = ranges.iter().find(|(start, end)| start > end) {
+                    return Err(GlobErrorKind::Invalid(format!(
+                        "invalid range; '{start}' > '{end}'"
+                    )));
+                }
+            }
+            Token::Alternation(branches) => {
+                for branch in branches {
+                    validate(branch)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The number of globs the `globset` backend would compile `tokens` into, counting every `**`
+/// embedded in a path component twice, so that [GlobLimits::max_expansions] rejects the same
+/// patterns with either backend.
+fn expansions(tokens: &[Token], starts_component: bool, ends_component: bool) -> usize {
+    let is_separator = |i: Option<usize>, edge: bool| {
+        i.and_then(|i| tokens.get(i)).map_or(edge, |token| *token == Token::Literal('/'))
+    };
+    tokens.iter().enumerate().fold(1usize, |count, (i, token)| {
+        let factor = match token {
+            Token::Alternation(branches) => branches
+                .iter()
+                .fold(0usize, |sum, branch| sum.saturating_add(expansions(branch, false, false))),
+            Token::Range(start, end) => {
+                usize::try_from(end.abs_diff(*start)).unwrap_or(usize::MAX).saturating_add(1)
+            }
+            Token::DoubleStar
+                if !(is_separator(i.checked_sub(1), starts_component)
+                    && is_separator(Some(i + 1), ends_component)) =>
+            {
+                2
+            }
+            _ => 1,
+        };
+        count.saturating_mul(factor)
+    })
+}
@@ -0,0 +1,81 @@
+use super::{GlobLimits, MatchOptions, Token, tokens};
+use crate::{EditorConfig, EditorConfigSection};
+
+/// Matching a pattern costing more than this is likely to slow down resolving noticeably.
+const SLOW_COST: usize = 256;
+
+/// An estimate of how expensive a section pattern is to match, see [complexity].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Complexity {
+    /// The number of patterns brace expansion produces, e.g. 4 for `{a,b}{c,d}`.
+    pub expansions: usize,
+
+    /// The most `*` and `**` in one expansion, including the implicit `**/` of patterns without
+    /// a `/`. Each may backtrack over the rest of the path.
+    pub wildcards: usize,
+}
+
+impl Complexity {
+    /// A relative estimate of the work to match one path.
+    pub fn cost(&self) -> usize {
+        self.expansions.saturating_mul(self.wildcards.max(1))
+    }
+
+    /// Whether the pattern is likely to slow down every resolve, e.g. deeply nested braces
+    /// over `**`.
+    pub fn is_slow(&self) -> bool {
+        self.cost() > SLOW_COST
+    }
+}
+
+/// Estimate the cost of matching paths against a section pattern.
+pub fn complexity(pattern: &str) -> Complexity {
+    fn visit(tokens: &[Token]) -> Complexity {
+        tokens.iter().fold(Complexity { expansions: 1, wildcards: 0 }, |total, token| {
+            let complexity = match token {
+                Token::Star | Token::DoubleStar => Complexity { expansions: 1, wildcards: 1 },
+                Token::Alternation(branches) => branches.iter().map(|branch| visit(branch)).fold(
+                    Complexity { expansions: 0, wildcards: 0 },
+                    |sum, branch| Complexity {
+                        expansions: sum.expansions.saturating_add(branch.expansions),
+                        wildcards: sum.wildcards.max(branch.wildcards),
+                    },
+                ),
+                Token::Range(start, end) => Complexity {
+                    expansions: usize::try_from(end.abs_diff(*start))
+                        .unwrap_or(usize::MAX)
+                        .saturating_add(1),
+                    wildcards: 0,
+                },
+                _ => Complexity { expansions: 1, wildcards: 0 },
+            };
+            Complexity {
+                expansions: total.expansions.saturating_mul(complexity.expansions),
+                wildcards: total.wildcards.saturating_add(complexity.wildcards),
+            }
+        })
+    }
+    let limits = GlobLimits { max_pattern_len: usize::MAX, max_expansions: usize::MAX };
+    tokens(pattern, limits, MatchOptions::default())
+        .map(|tokens| visit(&tokens))
+        .unwrap_or_default()
+}
+
+impl EditorConfigSection {
+    /// Estimate the cost of matching this section's pattern. See [complexity].
+    pub fn complexity(&self) -> Complexity {
+        complexity(&self.name)
+    }
+}
+
+impl EditorConfig {
+    /// The sections whose patterns are likely to slow down every resolve,
+    /// see [Complexity::is_slow].
+    pub fn slow_sections(&self) -> Vec<(&EditorConfigSection, Complexity)> {
+        self.sections
+            .iter()
+            .map(|section| (section, section.complexity()))
+            .filter(|(_, complexity)| complexity.is_slow())
+            .collect()
+    }
+}
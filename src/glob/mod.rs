@@ -0,0 +1,634 @@
+//! EditorConfig glob expressions.
+//!
+//! <https://spec.editorconfig.org/index.html#glob-expressions>
+//!
+//! Patterns are parsed here and matched by one of two backends: by default they are translated
+//! into `globset` globs, and without the `globset` feature they are matched directly by a
+//! built-in matcher that follows editorconfig-core. Patterns with numeric ranges are always
+//! matched by the built-in matcher, see [translate].
+
+mod builtin;
+mod complexity;
+#[cfg(feature = "globset")]
+mod translate;
+
+#[cfg(feature = "globset")]
+use std::cell::OnceCell;
+use std::{collections::HashMap, fmt, path::Path, sync::Arc};
+
+#[cfg(not(feature = "globset"))]
+use builtin::Matcher;
+#[cfg(feature = "globset")]
+use translate::Matcher;
+
+#[cfg(feature = "unstable")]
+use crate::corpus::GlobFeature;
+use crate::diagnostic::{self, Message};
+use crate::path;
+pub use complexity::{Complexity, complexity};
+#[cfg(feature = "ignore")]
+pub(crate) use translate::globs;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Literal(char),
+    /// `?`: any single character except `/`.
+    Any,
+    /// `*`: any string of characters except `/`.
+    Star,
+    /// `**`: any string of characters.
+    DoubleStar,
+    /// `[name]` or `[!name]`.
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    /// `{s1,s2,s3}`.
+    Alternation(Vec<Vec<Token>>),
+    /// `{num1..num2}`: any integer between `num1` and `num2`.
+    Range(i64, i64),
+}
+
+/// An EditorConfig section pattern, compiled for matching paths relative to the config file's
+/// directory.
+///
+/// As in editorconfig-core, wildcards match a leading `.`, so `[*]` and `[*.yml]` apply to
+/// dotfiles such as `.eslintrc.yml`.
+///
+/// A pattern ending in `/`, such as `[src/]`, matches every file under that directory,
+/// like `[src/**]`.
+#[derive(Debug, Clone)]
+pub struct EditorConfigGlob {
+    pattern: Arc<str>,
+    /// Shared by clones, see [GlobInterner].
+    strategy: Arc<Strategy>,
+}
+
+/// Compiles each distinct pattern once, so that sections repeating a pattern share one matcher.
+/// Every pattern must be compiled with the same options.
+#[derive(Debug, Default)]
+pub(crate) struct GlobInterner {
+    globs: HashMap<String, Result<EditorConfigGlob, GlobError>>,
+}
+
+impl GlobInterner {
+    pub(crate) fn compile(
+        &mut self,
+        pattern: &str,
+        case_insensitive: bool,
+        limits: GlobLimits,
+        options: MatchOptions,
+    ) -> Result<EditorConfigGlob, GlobError> {
+        if let Some(glob) = self.globs.get(pattern) {
+            return glob.clone();
+        }
+        let glob = EditorConfigGlob::compile(pattern, case_insensitive, limits, options);
+        self.globs.insert(pattern.to_string(), glob.clone());
+        glob
+    }
+}
+
+/// How a pattern is matched: most sections are `[*]` or `[*.ext]`,
+/// which are matched without running the glob engine.
+#[derive(Debug, Clone)]
+enum Strategy {
+    /// `*`: every path.
+    Everything,
+    /// `*.ext`: every file name ending with `.ext`, given with its leading `.`.
+    Extension(Box<[u8]>),
+    Glob(Matcher),
+}
+
+impl Strategy {
+    fn fast_path(pattern: &str) -> Option<Self> {
+        if pattern == "*" {
+            return Some(Self::Everything);
+        }
+        let extension = pattern.strip_prefix("*.")?;
+        let is_plain = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-' | b'+');
+        (!extension.is_empty() && extension.bytes().all(is_plain))
+            .then(|| Self::Extension(pattern.as_bytes()[1..].into()))
+    }
+}
+
+/// A path, as bytes separated by `/`, prepared for matching against many globs.
+pub(crate) struct Candidate<'a> {
+    path: &'a [u8],
+    #[cfg(feature = "globset")]
+    globset: OnceCell<globset::Candidate<'a>>,
+}
+
+impl<'a> Candidate<'a> {
+    pub(crate) fn from_bytes(path: &'a [u8]) -> Self {
+        Self {
+            path,
+            #[cfg(feature = "globset")]
+            globset: OnceCell::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GlobError {
+    pub pattern: String,
+    pub kind: GlobErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GlobErrorKind {
+    /// The pattern is not a valid glob.
+    Invalid(String),
+    /// The pattern exceeds [GlobLimits::max_pattern_len].
+    TooLong { max: usize },
+    /// The pattern exceeds [GlobLimits::max_expansions].
+    TooManyExpansions { max: usize },
+}
+
+impl fmt::Display for GlobErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(reason) => f.write_str(reason),
+            Self::TooLong { max } => write!(f, "pattern is longer than {max} characters"),
+            Self::TooManyExpansions { max } => {
+                write!(f, "pattern expands to more than {max} alternatives")
+            }
+        }
+    }
+}
+
+impl Message for GlobError {
+    fn id(&self) -> &'static str {
+        "invalid-glob"
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        vec![("pattern", self.pattern.clone()), ("reason", self.kind.to_string())]
+    }
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&diagnostic::render(self, diagnostic::english))
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+/// Limits guarding against patterns that are expensive to compile,
+/// e.g. from a hostile `.editorconfig` with huge brace alternations.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct GlobLimits {
+    /// Maximum length of a pattern in characters.
+    pub max_pattern_len: usize,
+
+    /// Maximum number of alternatives a pattern expands to,
+    /// e.g. `{a,b}.{c,d}` expands to 4 alternatives.
+    pub max_expansions: usize,
+}
+
+impl Default for GlobLimits {
+    fn default() -> Self {
+        // editorconfig-core limits section names to 4096 bytes.
+        Self { max_pattern_len: 4096, max_expansions: 1024 }
+    }
+}
+
+/// How section globs are compiled, to adapt matching to editor-specific quirks.
+/// The defaults follow the EditorConfig specification.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MatchOptions {
+    /// Whether `*`, `?` and `[name]` never match `/`.
+    pub literal_separator: bool,
+
+    /// Whether `\` escapes the next character, rather than being a literal `\`.
+    pub backslash_escape: bool,
+
+    /// Whether an empty alternative, e.g. in `foo{,.txt}`, matches the empty string.
+    /// Otherwise it is ignored, and `foo{,.txt}` only matches `foo.txt`.
+    pub empty_alternates: bool,
+
+    /// Whether bracket expressions accept POSIX classes such as `[[:alpha:]]` and `[[:digit:]]`,
+    /// as some editors do. This extension is off by default, and with the `spec-strict` feature.
+    pub posix_classes: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            literal_separator: true,
+            backslash_escape: true,
+            empty_alternates: true,
+            posix_classes: false,
+        }
+    }
+}
+
+impl EditorConfigGlob {
+    /// Compile an EditorConfig glob, e.g. `*.{js,ts}` or `src/**/*.rs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern cannot be compiled, e.g. for a class with a reversed
+    /// range such as `[z-a]`, or if it exceeds the default [GlobLimits].
+    pub fn new(pattern: &str) -> Result<Self, GlobError> {
+        Self::compile(pattern, false, GlobLimits::default(), MatchOptions::default())
+    }
+
+    pub(crate) fn compile(
+        pattern: &str,
+        case_insensitive: bool,
+        limits: GlobLimits,
+        options: MatchOptions,
+    ) -> Result<Self, GlobError> {
+        let fast_path = if case_insensitive { None } else { Strategy::fast_path(pattern) };
+        let strategy = match fast_path {
+            Some(strategy) => strategy,
+            None => Matcher::compile(pattern, case_insensitive, limits, options)
+                .map(Strategy::Glob)
+                .map_err(|kind| GlobError { pattern: pattern.to_string(), kind })?,
+        };
+        Ok(Self { pattern: pattern.into(), strategy: Arc::new(strategy) })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether `path`, relative to the config file's directory and separated by `/`, or also `\`
+    /// on Windows, matches.
+    pub fn is_match<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.is_match_bytes(&path::to_bytes(path.as_ref()))
+    }
+
+    /// Like [EditorConfigGlob::is_match], for a path given as bytes which need not be UTF-8.
+    pub fn is_match_bytes(&self, path: &[u8]) -> bool {
+        self.is_match_candidate(&Candidate::from_bytes(path))
+    }
+
+    pub(crate) fn is_match_candidate(&self, candidate: &Candidate<'_>) -> bool {
+        match self.strategy.as_ref() {
+            Strategy::Everything => true,
+            Strategy::Extension(extension) => {
+                let name = candidate.path.rsplit(|b| *b == b'/').next().unwrap_or_default();
+                name.ends_with(extension)
+            }
+            Strategy::Glob(matcher) => matcher.is_match_candidate(candidate),
+        }
+    }
+}
+
+/// The glob syntax features used by a section name.
+#[cfg(feature = "unstable")]
+pub(crate) fn features(pattern: &str) -> Vec<GlobFeature> {
+    fn visit(tokens: &[Token], features: &mut Vec<GlobFeature>) {
+        for token in tokens {
+            let feature = match token {
+                Token::Literal(_) => continue,
+                Token::Any => GlobFeature::AnyChar,
+                Token::Star => GlobFeature::Star,
+                Token::DoubleStar => GlobFeature::DoubleStar,
+                Token::Class { .. } => GlobFeature::Class,
+                Token::Alternation(branches) => {
+                    for branch in branches {
+                        visit(branch, features);
+                    }
+                    GlobFeature::Alternation
+                }
+                Token::Range(..) => GlobFeature::NumericRange,
+            };
+            features.push(feature);
+        }
+    }
+    let mut features = vec![];
+    if pattern.contains('/') {
+        features.push(GlobFeature::Path);
+    }
+    visit(&Parser::new(pattern, MatchOptions::default()).parse(), &mut features);
+    features.sort_unstable();
+    features.dedup();
+    features
+}
+
+/// Whether `pattern` may match a path under the directory `dir`, given relative to the config
+/// file's directory. Only the literal leading components of the pattern are compared with `dir`,
+/// so this may return `true` for patterns that never match there.
+#[cfg(feature = "unstable")]
+pub(crate) fn may_match_under(pattern: &str, dir: &[u8], options: MatchOptions) -> bool {
+    let limits = GlobLimits { max_pattern_len: usize::MAX, max_expansions: usize::MAX };
+    let Ok(tokens) = tokens(pattern, limits, options) else {
+        return true;
+    };
+    let mut components = tokens.split(|token| *token == Token::Literal('/'));
+    for dir_component in dir.split(|b| *b == b'/').filter(|component| !component.is_empty()) {
+        let Some(component) = components.next() else {
+            return false;
+        };
+        let literal = component
+            .iter()
+            .map(|token| match token {
+                Token::Literal(c) => Some(*c),
+                _ => None,
+            })
+            .collect::<Option<String>>();
+        match literal {
+            Some(literal) if literal.as_bytes() != dir_component => return false,
+            Some(_) => {}
+            None => return true,
+        }
+    }
+    true
+}
+
+/// The extensions, without their `.`, of every file name `pattern` can match,
+/// or `None` if that is not known from its literal suffixes, e.g. for `*` or `Makefile`.
+pub(crate) fn extensions(pattern: &str, options: MatchOptions) -> Option<Vec<String>> {
+    /// Walk `tokens` backwards from `suffix`, the literal text known to follow them.
+    fn visit(tokens: &[Token], mut suffix: String, extensions: &mut Vec<String>) -> Option<()> {
+        for (i, token) in tokens.iter().enumerate().rev() {
+            match token {
+                Token::Literal('/') => return None,
+                Token::Literal('.') => {
+                    extensions.push(suffix);
+                    return (extensions.len() <= 64).then_some(());
+                }
+                Token::Literal(c) => suffix.insert(0, *c),
+                Token::Alternation(branches) => {
+                    for branch in branches {
+                        visit(
+                            &[&tokens[..i], branch.as_slice()].concat(),
+                            suffix.clone(),
+                            extensions,
+                        )?;
+                    }
+                    return Some(());
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+    let limits = GlobLimits::default();
+    let tokens = tokens(pattern, limits, options).ok()?;
+    let mut extensions = vec![];
+    visit(&tokens, String::new(), &mut extensions)?;
+    Some(extensions)
+}
+
+/// Every file name a pattern without a `/` or wildcards can match, e.g. `package.json` and
+/// `Makefile` for `{package.json,Makefile}`, or `None` for other patterns.
+pub(crate) fn file_names(pattern: &str, options: MatchOptions) -> Option<Vec<String>> {
+    fn visit(tokens: &[Token], mut prefix: String, names: &mut Vec<String>) -> Option<()> {
+        let Some((token, rest)) = tokens.split_first() else {
+            names.push(prefix);
+            return (names.len() <= 64).then_some(());
+        };
+        match token {
+            Token::Literal('/') => None,
+            Token::Literal(c) => {
+                prefix.push(*c);
+                visit(rest, prefix, names)
+            }
+            Token::Alternation(branches) => {
+                for branch in branches {
+                    visit(&[branch.as_slice(), rest].concat(), prefix.clone(), names)?;
+                }
+                Some(())
+            }
+            _ => None,
+        }
+    }
+    if pattern.contains('/') {
+        return None;
+    }
+    let limits = GlobLimits::default();
+    let tokens = tokens(pattern, limits, options).ok()?;
+    let mut names = vec![];
+    // Skip the implicit `**/`.
+    visit(tokens.get(2..)?, String::new(), &mut names)?;
+    Some(names)
+}
+
+/// Parse a section name, applying the limits that do not depend on the backend.
+fn tokens(
+    mut pattern: &str,
+    limits: GlobLimits,
+    options: MatchOptions,
+) -> Result<Vec<Token>, GlobErrorKind> {
+    if pattern.chars().count() > limits.max_pattern_len {
+        return Err(GlobErrorKind::TooLong { max: limits.max_pattern_len });
+    }
+    let mut tokens = vec![];
+    // A pattern without a `/` matches the file name in any directory,
+    // otherwise it is anchored to the config file's directory, with or without a leading `/`.
+    if let Some(anchored) = pattern.strip_prefix('/') {
+        pattern = anchored;
+    } else if !pattern.contains('/') {
+        tokens.extend([Token::DoubleStar, Token::Literal('/')]);
+    }
+    tokens.extend(Parser::new(pattern, options).parse());
+    // A trailing `/` matches every file under the directory, as an extension of the specification.
+    if !cfg!(feature = "spec-strict")
+        && tokens.last() == Some(&Token::Literal('/'))
+        && !(options.backslash_escape && pattern.ends_with("\\/"))
+    {
+        tokens.push(Token::DoubleStar);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    options: MatchOptions,
+}
+
+impl Parser {
+    fn new(pattern: &str, options: MatchOptions) -> Self {
+        Self { chars: pattern.chars().collect(), pos: 0, options }
+    }
+
+    fn parse(mut self) -> Vec<Token> {
+        let mut branches = self.parse_branches(false);
+        debug_assert_eq!(branches.len(), 1);
+        branches.pop().unwrap_or_default()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.get(self.pos).copied();
+        self.pos += 1;
+        c
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Parse tokens until the end of the pattern, or until the closing `}` when `nested`.
+    /// Top-level commas separate branches only when `nested`.
+    fn parse_branches(&mut self, nested: bool) -> Vec<Vec<Token>> {
+        let mut branches = vec![vec![]];
+        while let Some(c) = self.bump() {
+            let token = match c {
+                '\\' if self.options.backslash_escape => {
+                    Token::Literal(self.bump().unwrap_or('\\'))
+                }
+                '?' => Token::Any,
+                '*' => {
+                    if self.peek() == Some('*') {
+                        while self.peek() == Some('*') {
+                            self.pos += 1;
+                        }
+                        Token::DoubleStar
+                    } else {
+                        Token::Star
+                    }
+                }
+                '[' => self.parse_class().unwrap_or(Token::Literal('[')),
+                '{' => match self.scan_braces() {
+                    Some(true) => Token::Alternation(self.parse_branches(true)),
+                    Some(false) => match self.parse_range() {
+                        Some(range) => range,
+                        // `{single}` is taken literally.
+                        None => {
+                            let tokens = self.parse_branches(true).concat();
+                            let last = branches.len() - 1;
+                            branches[last].push(Token::Literal('{'));
+                            branches[last].extend(tokens);
+                            Token::Literal('}')
+                        }
+                    },
+                    None => Token::Literal('{'),
+                },
+                ',' if nested => {
+                    branches.push(vec![]);
+                    continue;
+                }
+                '}' if nested => {
+                    if !self.options.empty_alternates {
+                        branches.retain(|branch| !branch.is_empty());
+                    }
+                    return branches;
+                }
+                c => Token::Literal(c),
+            };
+            let last = branches.len() - 1;
+            branches[last].push(token);
+        }
+        branches
+    }
+
+    /// Look ahead from just after a `{` for its matching `}`.
+    /// Returns whether the braces contain a top-level comma, or `None` if they are unclosed.
+    fn scan_braces(&self) -> Option<bool> {
+        let mut depth = 0usize;
+        let mut has_comma = false;
+        let mut chars = self.chars[self.pos..].iter();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if self.options.backslash_escape => {
+                    chars.next();
+                }
+                '{' => depth += 1,
+                '}' if depth == 0 => return Some(has_comma),
+                '}' => depth -= 1,
+                ',' if depth == 0 => has_comma = true,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Parse `num1..num2}` after a `{`, leaving the position untouched if it is not a range.
+    fn parse_range(&mut self) -> Option<Token> {
+        let end = self.chars[self.pos..].iter().position(|c| *c == '}')? + self.pos;
+        let content = self.chars[self.pos..end].iter().collect::<String>();
+        let (start, end_num) = content.split_once("..")?;
+        let parse = |num: &str| {
+            let digits = num.strip_prefix(['+', '-']).unwrap_or(num);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            num.parse::<i64>().ok()
+        };
+        let range = Token::Range(parse(start)?, parse(end_num)?);
+        self.pos = end + 1;
+        Some(range)
+    }
+
+    /// Parse a bracket expression after its opening `[`.
+    /// Returns `None` (leaving the position untouched) if it is unclosed or contains a `/`,
+    /// in which case the `[` is taken literally.
+    fn parse_class(&mut self) -> Option<Token> {
+        let start = self.pos;
+        let negated = self.peek() == Some('!');
+        if negated {
+            self.pos += 1;
+        }
+        let mut chars = vec![];
+        let mut ranges = vec![];
+        loop {
+            if self.options.posix_classes
+                && !cfg!(feature = "spec-strict")
+                && let Some(class) = self.parse_posix_class()
+            {
+                ranges.extend_from_slice(class);
+                continue;
+            }
+            match self.bump() {
+                None | Some('/') => {
+                    self.pos = start;
+                    return None;
+                }
+                Some(']') if !chars.is_empty() || !ranges.is_empty() => break,
+                Some('\\') if self.options.backslash_escape => match self.bump() {
+                    Some(c) => chars.push((c, true)),
+                    None => {
+                        self.pos = start;
+                        return None;
+                    }
+                },
+                Some(c) => chars.push((c, false)),
+            }
+        }
+        let mut i = 0;
+        while i < chars.len() {
+            let (c, _) = chars[i];
+            match chars.get(i + 1..i + 3) {
+                Some([('-', false), (end, _)]) => {
+                    ranges.push((c, *end));
+                    i += 3;
+                }
+                _ => {
+                    ranges.push((c, c));
+                    i += 1;
+                }
+            }
+        }
+        Some(Token::Class { negated, ranges })
+    }
+
+    /// Parse a POSIX class such as `[:alpha:]` inside a bracket expression.
+    /// Returns `None` (leaving the position untouched) if there is none, or it is unknown.
+    fn parse_posix_class(&mut self) -> Option<&'static [(char, char)]> {
+        let rest = self.chars.get(self.pos..)?;
+        let name = rest.strip_prefix(&['[', ':'])?;
+        let end = name.windows(2).position(|window| window == [':', ']'])?;
+        let ranges: &[(char, char)] = match name[..end].iter().collect::<String>().as_str() {
+            "alnum" => &[('0', '9'), ('A', 'Z'), ('a', 'z')],
+            "alpha" => &[('A', 'Z'), ('a', 'z')],
+            "blank" => &[(' ', ' '), ('\t', '\t')],
+            "cntrl" => &[('\0', '\x1f'), ('\x7f', '\x7f')],
+            "digit" => &[('0', '9')],
+            "graph" => &[('!', '~')],
+            "lower" => &[('a', 'z')],
+            "print" => &[(' ', '~')],
+            "punct" => &[('!', '/'), (':', '@'), ('[', '`'), ('{', '~')],
+            "space" => &[(' ', ' '), ('\t', '\r')],
+            "upper" => &[('A', 'Z')],
+            "xdigit" => &[('0', '9'), ('A', 'F'), ('a', 'f')],
+            _ => return None,
+        };
+        self.pos += end + 4;
+        Some(ranges)
+    }
+}
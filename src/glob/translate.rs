@@ -0,0 +1,265 @@
+//! The `globset` backend: patterns are translated into `globset` globs.
+//!
+//! `globset` only understands `**` as a whole path component and does not support nested
+//! braces, so braces are expanded, and every `**` that is embedded in a path component
+//! (e.g. `a**z` or `lib/**.js`) is rewritten into an equivalent alternation of globs that
+//! `globset` can compile.
+//!
+//! Like editorconfig-core, `{1..3}` matches any integer in the range, including `02` and `+2`.
+//! Globs cannot express that, so patterns with numeric ranges use the built-in matcher.
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use super::{Candidate, GlobErrorKind, GlobLimits, MatchOptions, Token, builtin, tokens};
+
+#[derive(Debug, Clone)]
+pub(super) enum Matcher {
+    Globs(GlobSet),
+    /// For patterns with numeric ranges.
+    Builtin(builtin::Matcher),
+}
+
+impl Matcher {
+    pub(super) fn compile(
+        pattern: &str,
+        case_insensitive: bool,
+        limits: GlobLimits,
+        options: MatchOptions,
+    ) -> Result<Self, GlobErrorKind> {
+        if has_range(&tokens(pattern, limits, options)?) {
+            return builtin::Matcher::compile(pattern, case_insensitive, limits, options)
+                .map(Self::Builtin);
+        }
+        let invalid = |err: globset::Error| GlobErrorKind::Invalid(err.kind().to_string());
+        // `globset` has no special case for dotfiles.
+        let mut builder = GlobSetBuilder::new();
+        for glob in globs(pattern, limits, options)? {
+            builder.add(
+                GlobBuilder::new(&glob)
+                    .literal_separator(options.literal_separator)
+                    // Literals are always emitted with `\` escapes.
+                    .backslash_escape(true)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(invalid)?,
+            );
+        }
+        builder.build().map(Self::Globs).map_err(invalid)
+    }
+
+    pub(super) fn is_match_candidate(&self, candidate: &Candidate<'_>) -> bool {
+        match self {
+            Self::Globs(globs) => {
+                let path = candidate.path;
+                globs.is_match_candidate(
+                    candidate.globset.get_or_init(|| globset::Candidate::from_bytes(path)),
+                )
+            }
+            Self::Builtin(matcher) => matcher.is_match_candidate(candidate),
+        }
+    }
+}
+
+fn has_range(tokens: &[Token]) -> bool {
+    tokens.iter().any(|token| match token {
+        Token::Range(..) => true,
+        Token::Alternation(branches) => branches.iter().any(|branch| has_range(branch)),
+        _ => false,
+    })
+}
+
+/// Translate a section name into `globset` globs; a path matches the section if it matches any of them.
+/// Numeric ranges only match numbers without a sign or leading zeros here.
+pub(crate) fn globs(
+    pattern: &str,
+    limits: GlobLimits,
+    options: MatchOptions,
+) -> Result<Vec<String>, GlobErrorKind> {
+    let max = limits.max_expansions;
+    let too_many = || GlobErrorKind::TooManyExpansions { max };
+    let mut globs = vec![];
+    for tokens in expand(&tokens(pattern, limits, options)?, max).ok_or_else(too_many)? {
+        let split = split_double_stars(tokens, max - globs.len()).ok_or_else(too_many)?;
+        globs.extend(split.into_iter().map(|tokens| emit(tokens, options.literal_separator)));
+    }
+    Ok(globs)
+}
+
+/// Expand alternations into their cartesian product of alternation-free token lists.
+/// Returns `None` if there would be more than `max` of them.
+fn expand(tokens: &[Token], max: usize) -> Option<Vec<Vec<Token>>> {
+    let mut results = vec![vec![]];
+    for token in tokens {
+        // A class of only `!` and `^` cannot be written without starting with either, which
+        // would negate it, so it is matched as an alternation of the characters instead.
+        let alternation;
+        let token = match token {
+            Token::Class { negated: false, ranges }
+                if ranges.iter().all(|range| matches!(range, ('!', '!') | ('^', '^'))) =>
+            {
+                let branches = ranges.iter().map(|(c, _)| vec![Token::Literal(*c)]).collect();
+                alternation = Token::Alternation(branches);
+                &alternation
+            }
+            token => token,
+        };
+        match token {
+            Token::Alternation(branches) => {
+                let mut expanded = vec![];
+                for branch in branches {
+                    expanded.extend(expand(branch, max)?);
+                }
+                if results.len().saturating_mul(expanded.len()) > max {
+                    return None;
+                }
+                results = results
+                    .iter()
+                    .flat_map(|prefix| {
+                        expanded.iter().map(move |suffix| [prefix.as_slice(), suffix].concat())
+                    })
+                    .collect();
+            }
+            Token::Range(start, end) => {
+                let count = end.abs_diff(*start).saturating_add(1);
+                if u64::try_from(results.len()).unwrap_or(u64::MAX).saturating_mul(count)
+                    > u64::try_from(max).unwrap_or(u64::MAX)
+                {
+                    return None;
+                }
+                results = results
+                    .iter()
+                    .flat_map(|prefix| {
+                        (*start.min(end)..=*start.max(end)).map(move |n| {
+                            let digits =
+                                n.to_string().chars().map(Token::Literal).collect::<Vec<_>>();
+                            [prefix.as_slice(), &digits].concat()
+                        })
+                    })
+                    .collect();
+            }
+            token => {
+                for result in &mut results {
+                    result.push(token.clone());
+                }
+            }
+        }
+    }
+    Some(results)
+}
+
+/// `globset` only treats `**` as "any string" when it is a whole path component.
+/// An embedded `X**Y` is equivalent to `X*Y` (no `/` matched) or `X*/**/*Y` (at least one `/` matched).
+/// Returns `None` if there would be more than `max` alternatives.
+fn split_double_stars(tokens: Vec<Token>, max: usize) -> Option<Vec<Vec<Token>>> {
+    let is_separator = |token: Option<&Token>| token.is_none_or(|t| *t == Token::Literal('/'));
+    let mut pending = vec![tokens];
+    let mut results = vec![];
+    while let Some(tokens) = pending.pop() {
+        let embedded = tokens.iter().enumerate().position(|(i, token)| {
+            *token == Token::DoubleStar
+                && !(is_separator(i.checked_sub(1).and_then(|i| tokens.get(i)))
+                    && is_separator(tokens.get(i + 1)))
+        });
+        let Some(i) = embedded else {
+            if results.len() == max {
+                return None;
+            }
+            results.push(tokens);
+            continue;
+        };
+        if results.len() + pending.len() + 2 > max {
+            return None;
+        }
+        let (prefix, suffix) = (&tokens[..i], &tokens[i + 1..]);
+        pending.push([prefix, &[Token::Star], suffix].concat());
+        pending.push(
+            [
+                prefix,
+                &[
+                    Token::Star,
+                    Token::Literal('/'),
+                    Token::DoubleStar,
+                    Token::Literal('/'),
+                    Token::Star,
+                ],
+                suffix,
+            ]
+            .concat(),
+        );
+    }
+    Some(results)
+}
+
+fn emit(tokens: Vec<Token>, literal_separator: bool) -> String {
+    let mut glob = String::new();
+    for token in tokens {
+        match token {
+            Token::Literal(c) => emit_literal(&mut glob, c),
+            Token::Any => glob.push('?'),
+            Token::Star => glob.push('*'),
+            Token::DoubleStar => glob.push_str("**"),
+            Token::Class { negated: false, ranges }
+                if ranges.len() == 1 && ranges[0].0 == ranges[0].1 =>
+            {
+                emit_literal(&mut glob, ranges[0].0);
+            }
+            Token::Class { negated, ranges } => {
+                emit_class(&mut glob, negated && literal_separator, negated, ranges);
+            }
+            Token::Alternation(_) | Token::Range(..) => {
+                unreachable!("alternations are expanded before emitting")
+            }
+        }
+    }
+    glob
+}
+
+fn emit_literal(glob: &mut String, c: char) {
+    if matches!(c, '*' | '?' | '[' | ']' | '{' | '}' | '\\' | ',' | '!' | '#') {
+        glob.push('\\');
+    }
+    glob.push(c);
+}
+
+/// `globset` classes do not support escapes: `]` must come first, and `-` last.
+/// With `exclude_separator`, a negated class never matches the path separator.
+fn emit_class(
+    glob: &mut String,
+    exclude_separator: bool,
+    negated: bool,
+    mut ranges: Vec<(char, char)>,
+) {
+    if exclude_separator {
+        ranges.push(('/', '/'));
+    }
+    ranges.sort_by_key(|range| match range {
+        (']', ']') => 0,
+        ('-', '-') => 3,
+        ('!' | '^', _) => 2,
+        _ => 1,
+    });
+    // A leading `!` or `^` would negate the class. Then every range starts with either, or
+    // is `-`, which may lead instead, or the first character of a range is split off.
+    if !negated && matches!(ranges.first(), Some(('!' | '^', _))) {
+        if ranges.last() == Some(&('-', '-')) {
+            ranges.rotate_right(1);
+        } else if let Some(i) = ranges.iter().position(|(start, end)| start < end) {
+            let (start, end) = ranges.remove(i);
+            let next = char::from_u32(u32::from(start) + 1).unwrap_or(end);
+            ranges.insert(0, (next, end));
+            ranges.push((start, start));
+        }
+    }
+    glob.push('[');
+    if negated {
+        glob.push('!');
+    }
+    for (start, end) in ranges {
+        glob.push(start);
+        if start != end {
+            glob.push('-');
+            glob.push(end);
+        }
+    }
+    glob.push(']');
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::{EditorConfig, PatternKind, glob};
+
+/// Sections indexed by the extensions of the files they can match, e.g. `[*.{js,jsx}]` under
+/// `js` and `jsx`, so that resolving a path skips sections for other extensions.
+/// Sections matching files by name, such as `[Makefile]`, are indexed by the file name instead.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ExtensionIndex {
+    by_extension: HashMap<Box<[u8]>, Vec<usize>>,
+
+    by_name: HashMap<Box<[u8]>, Vec<usize>>,
+
+    /// Sections that may match files with any extension, or none.
+    unindexed: Vec<usize>,
+}
+
+impl ExtensionIndex {
+    pub(crate) fn new(config: &EditorConfig) -> Self {
+        let mut index = Self::default();
+        for (i, section) in config.sections.iter().enumerate() {
+            if section.matcher.is_none() {
+                continue;
+            }
+            let kind = section.pattern_kind();
+            if config.case_insensitive || kind == PatternKind::MatchAll {
+                index.unindexed.push(i);
+                continue;
+            }
+            if kind == PatternKind::FilenameOnly
+                && let Some(mut names) = glob::file_names(&section.name, config.match_options)
+            {
+                names.sort_unstable();
+                names.dedup();
+                for name in names {
+                    index.by_name.entry(name.into_bytes().into()).or_default().push(i);
+                }
+                continue;
+            }
+            let Some(mut extensions) = glob::extensions(&section.name, config.match_options) else {
+                index.unindexed.push(i);
+                continue;
+            };
+            extensions.sort_unstable();
+            extensions.dedup();
+            for extension in extensions {
+                index.by_extension.entry(extension.into_bytes().into()).or_default().push(i);
+            }
+        }
+        index
+    }
+
+    /// The indices of the sections that may match `path`, in order.
+    pub(crate) fn candidates(&self, path: &[u8]) -> Vec<usize> {
+        let name = path.rsplit(|b| *b == b'/').next().unwrap_or_default();
+        let indexed = name
+            .iter()
+            .rposition(|b| *b == b'.')
+            .and_then(|dot| self.by_extension.get(&name[dot + 1..]))
+            .map_or(&[][..], Vec::as_slice);
+        let named = self.by_name.get(name).map_or(&[][..], Vec::as_slice);
+        let mut candidates = [self.unindexed.as_slice(), indexed, named].concat();
+        candidates.sort_unstable();
+        candidates
+    }
+}
@@ -0,0 +1,62 @@
+//! The `ij_*` properties written by JetBrains IDEs, see [EditorConfigProperties::intellij].
+//!
+//! <https://www.jetbrains.com/help/idea/editorconfig.html>
+
+use std::collections::BTreeMap;
+
+use crate::EditorConfigProperties;
+
+/// `ij_*` options that are not specific to a language.
+const GENERAL: [&str; 8] = [
+    "continuation_indent_size",
+    "formatter_enabled",
+    "formatter_off_tag",
+    "formatter_on_tag",
+    "formatter_tags_enabled",
+    "smart_tabs",
+    "visual_guides",
+    "wrap_on_typing",
+];
+
+/// The `ij_*` properties, with values as written.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct IntellijProperties {
+    /// Options for every language by name, e.g. `ij_visual_guides` as `visual_guides`.
+    pub general: BTreeMap<String, String>,
+
+    /// Options by language and name, e.g. `ij_java_imports_layout` as `java` and
+    /// `imports_layout`. The language `any` applies to every language.
+    pub languages: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl IntellijProperties {
+    /// The value of `option` for `language`, falling back to the `any` language.
+    pub fn get(&self, language: &str, option: &str) -> Option<&str> {
+        [language, "any"]
+            .into_iter()
+            .find_map(|language| self.languages.get(language)?.get(option))
+            .map(String::as_str)
+    }
+}
+
+impl EditorConfigProperties {
+    /// The `ij_*` properties in [EditorConfigProperties::unknown], grouped by language.
+    pub fn intellij(&self) -> IntellijProperties {
+        let mut properties = IntellijProperties::default();
+        for (key, value) in &self.unknown {
+            let Some(name) = key.strip_prefix("ij_") else {
+                continue;
+            };
+            if GENERAL.contains(&name) {
+                properties.general.insert(name.to_string(), value.clone());
+            } else if let Some((language, option)) = name.split_once('_') {
+                properties
+                    .languages
+                    .entry(language.to_string())
+                    .or_default()
+                    .insert(option.to_string(), value.clone());
+            }
+        }
+        properties
+    }
+}
@@ -1,20 +1,160 @@
-use std::path::{Path, PathBuf};
-
-use globset::{Glob, GlobMatcher};
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod builder;
+mod cache;
+mod cancel;
+mod case;
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub mod corpus;
+pub mod detect;
+pub mod diagnostic;
+#[cfg(feature = "unstable")]
+mod directory;
+mod document;
+#[cfg(feature = "extended-properties")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extended-properties")))]
+pub mod extended;
+mod extension;
+mod gitignore;
+mod glob;
+mod index;
+mod intellij;
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub mod matrix;
+#[cfg(feature = "ignore")]
+mod overrides;
+mod parser;
+mod path;
+mod property;
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub mod reduce;
+mod resolve;
+mod round_trip;
+mod semantics;
+mod serialize;
+mod span;
+mod spec_version;
+mod specificity;
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub mod store;
+mod telemetry;
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+mod trace;
+mod transaction;
+#[cfg(not(feature = "unstable"))]
+mod unstable;
+pub mod value;
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub mod workspace;
+
+use std::{
+    borrow::Cow,
+    fs,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Instant,
+};
+
+use cache::MergeCache;
+use glob::{Candidate, GlobInterner};
+use index::ExtensionIndex;
+use parser::{Parsed, Parser};
+use telemetry::Observer;
+
+pub use builder::{BuildError, EditorConfigBuilder, EditorConfigPropertiesBuilder};
+pub use cancel::Cancelled;
+pub use case::CaseMismatch;
+pub use diagnostic::{Diagnostic, DiagnosticKind, ParseError, ParseErrorKind};
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub use directory::DirectoryConfig;
+pub use document::EditorConfigDocument;
+pub use extension::PropertyRegistry;
+pub use gitignore::{GitignoreIsm, GitignoreIsmKind, gitignore_isms};
+pub use glob::{
+    Complexity, EditorConfigGlob, GlobError, GlobErrorKind, GlobLimits, MatchOptions, complexity,
+};
+pub use intellij::IntellijProperties;
+pub use property::{PropertyKey, PropertyValue, UnknownPropertyKey, ValueSyntax, completions};
+pub use resolve::{OutsideCwd, ResolveError, ResolveOptions};
+pub use round_trip::{RoundTripDivergence, RoundTripDivergenceKind};
+pub use semantics::{Semantics, semantics_version};
+pub use span::{LineKind, LineRecord, PairSpans, SectionSpans, Span};
+pub use spec_version::SpecVersion;
+pub use specificity::{PatternKind, Specificity};
+pub use telemetry::{ResolveEvent, ResolveObserver};
+pub use trace::{PropertySource, Traced};
+pub use transaction::{DocumentTransaction, EditViolation};
+#[cfg(not(feature = "unstable"))]
+pub use unstable::{RequiresUnstableFeature, corpus, matrix, reduce, store, workspace};
 
 #[derive(Debug, Default, Clone)]
 pub struct EditorConfig {
     /// Set to true to tell the core not to check any higher directory for EditorConfig settings for on the current filename.
-    root: bool,
+    /// `None` if the key is absent.
+    root: Option<bool>,
 
     sections: Vec<EditorConfigSection>,
 
+    /// Key-value pairs before the first section other than `root`, in source order.
+    preamble: Vec<(String, String)>,
+
+    /// The classified lines of the source text.
+    lines: Vec<LineRecord>,
+
     /// The base directory for resolving absolute paths.
     cwd: Option<PathBuf>,
+
+    /// The path of the `.editorconfig` file.
+    path: Option<PathBuf>,
+
+    /// The directory of the `.editorconfig` file, which section globs are relative to.
+    config_dir: Option<PathBuf>,
+
+    /// Whether section globs match paths case-insensitively.
+    case_insensitive: bool,
+
+    glob_limits: GlobLimits,
+
+    match_options: MatchOptions,
+
+    /// See [ParseOptions::spec_version].
+    spec_version: Option<SpecVersion>,
+
+    /// The other [ParseOptions] that change the result of resolving, for [EditorConfig::semantics].
+    core_length_limits: bool,
+    merge_duplicate_sections: bool,
+    inline_comments: bool,
+    input_limits: InputLimits,
+    max_line_length_off_aliases: Vec<String>,
+
+    /// Case-insensitive matchers for detecting case mismatches, compiled on first use.
+    case_folded_matchers: OnceLock<Vec<Option<EditorConfigGlob>>>,
+
+    /// Built on first use.
+    extension_index: OnceLock<ExtensionIndex>,
+
+    merge_cache: MergeCache,
+
+    observer: Observer,
 }
 
 impl EditorConfig {
     pub fn root(&self) -> bool {
+        self.root == Some(true)
+    }
+
+    /// The value of `root`, or `None` if it is not set, to tell an explicit `root = false`
+    /// apart from a missing key. Any value other than `true` is `false`.
+    pub fn root_value(&self) -> Option<bool> {
         self.root
     }
 
@@ -22,15 +162,178 @@ impl EditorConfig {
         &self.sections
     }
 
+    /// The section at `index` in [EditorConfig::sections].
+    pub fn section(&self, index: usize) -> Option<&EditorConfigSection> {
+        self.sections.get(index)
+    }
+
+    /// The first section named exactly `pattern`, e.g. `*.rs` for `[*.rs]`.
+    pub fn find_section(&self, pattern: &str) -> Option<&EditorConfigSection> {
+        self.section_index_of(pattern).map(|index| &self.sections[index])
+    }
+
+    /// The index in [EditorConfig::sections] of the first section named exactly `pattern`.
+    /// Later sections with the same name are reported as [DiagnosticKind::DuplicateSection].
+    pub fn section_index_of(&self, pattern: &str) -> Option<usize> {
+        self.sections.iter().position(|section| section.name == pattern)
+    }
+
+    /// Key-value pairs before the first section other than `root`, such as custom keys of
+    /// some tools, in source order.
+    pub fn preamble(&self) -> &[(String, String)] {
+        &self.preamble
+    }
+
     pub fn cwd(&self) -> Option<&Path> {
         self.cwd.as_deref()
     }
 
+    /// The path of the `.editorconfig` file, set by [EditorConfig::from_file] or
+    /// [EditorConfig::with_config_path].
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn config_dir(&self) -> Option<&Path> {
+        self.config_dir.as_deref()
+    }
+
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Create a builder for constructing a config programmatically.
+    pub fn builder() -> EditorConfigBuilder {
+        EditorConfigBuilder::default()
+    }
+
     /// Sets the current working directory for resolving absolute paths.
+    ///
+    /// Absolute paths outside `cwd` are matched as-is; see [EditorConfig::with_config_path]
+    /// for matching relative to the config file as the spec requires.
     pub fn with_cwd<P: AsRef<Path>>(mut self, cwd: P) -> Self {
         self.cwd = Some(cwd.as_ref().to_path_buf());
         self
     }
+
+    /// Sets the path of the `.editorconfig` file, so that section globs match paths relative
+    /// to its directory. This takes precedence over [EditorConfig::with_cwd].
+    ///
+    /// Absolute paths outside that directory match no section,
+    /// while relative paths are taken to be relative to it already.
+    ///
+    /// A relative `path`, e.g. `.editorconfig`, is relative to the `cwd` if it is set,
+    /// or else to the current directory of the process.
+    pub fn with_config_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        let path = path.as_ref();
+        let absolute = match &self.cwd {
+            _ if path.is_absolute() => path.to_path_buf(),
+            Some(cwd) => cwd.join(path),
+            None => std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+        };
+        self.config_dir = Some(absolute.parent().unwrap_or(Path::new("")).to_path_buf());
+        self.path = Some(path.to_path_buf());
+        self
+    }
+
+    /// Sets whether section globs match paths case-insensitively,
+    /// e.g. for case-insensitive filesystems where `[Makefile]` should match `makefile`.
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self.extension_index = OnceLock::new();
+        let mut globs = GlobInterner::default();
+        for section in &mut self.sections {
+            let compiled = globs.compile(
+                &section.name,
+                case_insensitive,
+                self.glob_limits,
+                self.match_options,
+            );
+            (section.matcher, section.glob_error) = match compiled {
+                Ok(matcher) => (Some(matcher), None),
+                Err(err) => (None, Some(err)),
+            };
+        }
+        self
+    }
+}
+
+/// Length limits from <https://spec.editorconfig.org/index.html#limits>, in characters,
+/// enforced with the `spec-strict` feature.
+const SPEC_MAX_SECTION_NAME_LEN: usize = 1024;
+const SPEC_MAX_KEY_LEN: usize = 1024;
+const SPEC_MAX_VALUE_LEN: usize = 4096;
+
+/// Options for [EditorConfig::parse_with_options].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Sections whose pattern exceeds these limits never match, and are reported as diagnostics.
+    pub glob_limits: GlobLimits,
+
+    pub match_options: MatchOptions,
+
+    /// Ignore pairs whose key is longer than 1024 bytes or whose value is longer than 4096 bytes,
+    /// as editorconfig-core does.
+    pub core_length_limits: bool,
+
+    /// Add the pairs of a section to the earlier section with the same name, so each name
+    /// appears once in [EditorConfig::sections]. Later keys still win, but this changes the
+    /// result where a section in between sets the same key.
+    /// Duplicates are reported as diagnostics either way.
+    pub merge_duplicate_sections: bool,
+
+    /// Fill in defaults after resolving as editorconfig-core does for this version, e.g.
+    /// `tab_width` defaults to a numeric `indent_size`. `None` leaves properties that are not set
+    /// as [EditorConfigProperty::None], leaving defaults to the editor.
+    pub spec_version: Option<SpecVersion>,
+
+    /// Strip comments after a value, such as `indent_size = 2 ; two spaces`, as older versions
+    /// of editorconfig-core did. A comment starts at a `;` or `#` after whitespace.
+    /// Ignored with the `spec-strict` feature.
+    pub inline_comments: bool,
+
+    /// Caps for parsing untrusted input, unlimited by default.
+    pub input_limits: InputLimits,
+
+    /// Values of `max_line_length` that also parse to [MaxLineLength::Off], case-insensitive,
+    /// e.g. `none` or `0` as some tools spell "no limit". Ignored with the `spec-strict` feature.
+    pub max_line_length_off_aliases: Vec<String>,
+}
+
+/// Caps on the size of the source text, so that parsing pathological input stays cheap.
+/// Each limit that is hit is reported as a diagnostic.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InputLimits {
+    /// Maximum length of the source text in bytes.
+    /// The line crossing the limit and every line after it are ignored.
+    pub max_input_len: usize,
+
+    /// Maximum number of sections.
+    /// The first header over the limit and every line after it are ignored.
+    pub max_sections: usize,
+
+    /// Maximum length of a line in bytes, without its line ending. Longer lines are ignored.
+    pub max_line_len: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self { max_input_len: usize::MAX, max_sections: usize::MAX, max_line_len: usize::MAX }
+    }
+}
+
+impl InputLimits {
+    /// Limits for services parsing user-provided files, well above what real-world configs need.
+    pub fn untrusted() -> Self {
+        Self { max_input_len: 1 << 20, max_sections: 4096, max_line_len: 8192 }
+    }
+}
+
+impl ParseOptions {
+    /// Options for resolving like the editorconfig-core reference implementation.
+    pub fn core_compat() -> Self {
+        Self { core_length_limits: true, ..Self::default() }
+    }
 }
 
 /// <https://spec.editorconfig.org/index.html>
@@ -39,9 +342,19 @@ pub struct EditorConfigSection {
     /// Section Name: the string between the beginning `[` and the ending `]`.
     pub name: String,
 
-    pub matcher: Option<GlobMatcher>,
+    pub matcher: Option<EditorConfigGlob>,
+
+    /// Why [EditorConfigSection::matcher] is `None`: the error compiling the section name.
+    pub glob_error: Option<GlobError>,
 
     pub properties: EditorConfigProperties,
+
+    /// Pairs whose key is not a property above, such as `quote_type`, in source order.
+    pub unknown_properties: Vec<(String, String)>,
+
+    /// Pairs whose key is a property above, with the value as written before parsing, including
+    /// invalid values, in source order. See [EditorConfigSection::raw_value].
+    pub raw_properties: Vec<(String, String)>,
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -63,7 +376,8 @@ pub struct EditorConfigProperties {
     /// Set to a whole number defining the number of columns used for each indentation level and the width of soft tabs (when supported).
     /// If this equals tab, the indent_size shall be set to the tab size, which should be tab_width (if specified); else, the tab size set by the editor.
     /// The values are case-insensitive.
-    pub indent_size: EditorConfigProperty<usize>,
+    /// [EditorConfig::resolve_with_spec_defaults] replaces [IndentSize::Tab] with the `tab_width`.
+    pub indent_size: EditorConfigProperty<IndentSize>,
 
     /// Set to a whole number defining the number of columns used to represent a tab character.
     /// This defaults to the value of indent_size and should not usually need to be specified.
@@ -89,6 +403,35 @@ pub struct EditorConfigProperties {
     /// Not part of spec <https://github.com/editorconfig/editorconfig-vscode/issues/53#issuecomment-462432616>
     /// But documented in <https://prettier.io/docs/next/configuration#editorconfig>
     pub max_line_length: EditorConfigProperty<MaxLineLength>,
+
+    /// Properties whose key is not a field above, such as `quote_type`, with the last value for
+    /// each key in order of first appearance, see [EditorConfigProperties::get_raw].
+    /// When resolving, `unset` removes a key.
+    pub unknown: Vec<(String, String)>,
+}
+
+/// Properties resolved for a path, see [EditorConfig::resolve_with_matches].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Resolved {
+    pub properties: EditorConfigProperties,
+
+    /// The number of sections that matched the path.
+    pub matched_sections: usize,
+}
+
+impl Resolved {
+    /// Whether any section matched the path.
+    pub fn is_matched(&self) -> bool {
+        self.matched_sections > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IndentSize {
+    /// A whole number of columns
+    Number(usize),
+    /// The tab size, which should be `tab_width` if specified
+    Tab,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -99,239 +442,502 @@ pub enum MaxLineLength {
     Off,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum IndentStyle {
     Tab,
     Space,
+    /// A value not in the specification, lowercased.
+    Other(String),
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum EndOfLine {
     Lf,
     Cr,
     Crlf,
+    /// A value not in the specification, lowercased, e.g. `native`.
+    Other(String),
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Charset {
     Latin1,
     Utf8,
     Utf8bom,
     Utf16be,
     Utf16le,
+    /// A value not in the specification, lowercased, e.g. `utf-16`.
+    Other(String),
 }
 
 impl EditorConfig {
     /// <https://spec.editorconfig.org/index.html#id6>
     pub fn parse(source_text: &str) -> Self {
-        // EditorConfig files are in an INI-like file format.
-        // To read an EditorConfig file, take one line at a time, from beginning to end.
-        // For each line:
-        // 1. Remove all leading and trailing whitespace.
-        // 2. Process the remaining text as specified for its type below.
-        let mut root = false;
-        let mut sections = vec![];
-        let mut preamble = true;
-        for line in source_text.lines() {
-            let line = line.trim();
-            // Blank: Contains nothing. Blank lines are ignored.
-            if line.is_empty() {
-                continue;
-            }
-            // Comment: starts with a ; or a #. Comment lines are ignored.
-            if line.starts_with([';', '#']) {
-                continue;
-            }
-            // Parse `root`. Must be specified in the preamble. The value is case-insensitive.
-            if preamble
-                && !line.starts_with('[')
-                && let Some((key, value)) = line.split_once('=')
-                && key.trim_end() == "root"
-                && value.trim_start().eq_ignore_ascii_case("true")
-            {
-                root = true;
-            }
-            // Section Header: starts with a [ and ends with a ]. These lines define globs;
-            if let Some(line) = line.strip_prefix('[') {
-                preamble = false;
-                if let Some(line) = line.strip_suffix(']') {
-                    let name = line.to_string();
-                    let matcher = Glob::new(&name).ok().map(|glob| glob.compile_matcher());
-                    sections.push(EditorConfigSection {
-                        name,
-                        matcher,
-                        ..EditorConfigSection::default()
-                    });
-                }
+        Self::parse_with_diagnostics(source_text).0
+    }
+
+    /// Parse the raw bytes of a file, decoding them as UTF-8 or UTF-16 with a byte order mark,
+    /// UTF-8, or else latin1, see [detect::charset].
+    pub fn parse_bytes(bytes: &[u8]) -> Self {
+        Self::parse(&detect::decode(bytes))
+    }
+
+    /// Parse like [EditorConfig::parse], also reporting problems that `parse` silently ignores:
+    /// malformed section headers, section headers whose glob failed to compile, invalid values
+    /// of known properties, and known properties before the first section.
+    ///
+    /// This is lenient: use [EditorConfig::try_parse] to fail on such problems instead.
+    pub fn parse_with_diagnostics(source_text: &str) -> (Self, Vec<Diagnostic>) {
+        Self::parse_with_options(source_text, &ParseOptions::default())
+    }
+
+    /// Parse with [ParseOptions], reporting problems like [EditorConfig::parse_with_diagnostics].
+    pub fn parse_with_options(
+        source_text: &str,
+        options: &ParseOptions,
+    ) -> (Self, Vec<Diagnostic>) {
+        let parsed = Self::parse_source(source_text, options, false);
+        (parsed.editor_config, parsed.diagnostics)
+    }
+
+    /// Parse like [EditorConfig::parse], failing on malformed section headers,
+    /// invalid values of known properties and globs that fail to compile.
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem found, in source order.
+    pub fn try_parse(source_text: &str) -> Result<Self, Vec<ParseError>> {
+        let parsed = Self::parse_source(source_text, &ParseOptions::default(), false);
+        if parsed.errors.is_empty() { Ok(parsed.editor_config) } else { Err(parsed.errors) }
+    }
+
+    fn parse_source(source_text: &str, options: &ParseOptions, record_spans: bool) -> Parsed {
+        let mut parser = Parser::new(options, record_spans);
+        // Some editors save files with a UTF-8 BOM, which is not part of the first line.
+        let text = source_text.strip_prefix('\u{feff}').unwrap_or(source_text);
+        for line in parser::lines(text) {
+            if parser.is_truncated() {
+                break;
             }
-            // Key-Value Pair (or Pair): contains a key and a value, separated by an `=`.
-            if let Some(section) = sections.last_mut()
-                && let Some((key, value)) = line.split_once('=')
-            {
-                let value = value.trim_start();
-                let properties = &mut section.properties;
-                match key.trim_end() {
-                    "indent_style" => {
-                        properties.indent_style = IndentStyle::parse(value);
-                    }
-                    "indent_size" => {
-                        properties.indent_size = EditorConfigProperty::<usize>::parse(value);
-                    }
-                    "tab_width" => {
-                        properties.tab_width = EditorConfigProperty::<usize>::parse(value);
-                    }
-                    "end_of_line" => {
-                        properties.end_of_line = EditorConfigProperty::<EndOfLine>::parse(value);
-                    }
-                    "charset" => {
-                        properties.charset = EditorConfigProperty::<Charset>::parse(value);
-                    }
-                    "trim_trailing_whitespace" => {
-                        properties.trim_trailing_whitespace =
-                            EditorConfigProperty::<bool>::parse(value);
-                    }
-                    "insert_final_newline" => {
-                        properties.insert_final_newline =
-                            EditorConfigProperty::<bool>::parse(value);
-                    }
-                    "max_line_length" => {
-                        properties.max_line_length =
-                            EditorConfigProperty::<MaxLineLength>::parse(value);
-                    }
-                    _ => {}
+            parser.line(line, offset(source_text, line));
+        }
+        parser.finish()
+    }
+
+    /// Read and parse the file at `path` like [EditorConfig::parse_bytes], and set it as the
+    /// config path, see [EditorConfig::with_config_path].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reading the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let bytes = fs::read(path.as_ref())?;
+        Ok(Self::parse_bytes(&bytes).with_config_path(path))
+    }
+
+    /// Parse like [EditorConfig::parse], reading one line at a time from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reading from `reader`, including for text that is not valid UTF-8.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> io::Result<Self> {
+        let options = ParseOptions::default();
+        let mut parser = Parser::new(&options, false);
+        let mut buf = String::new();
+        let mut start = 0;
+        while reader.read_line(&mut buf)? > 0 {
+            // `buf` holds several lines if they end with a lone `\r`.
+            for mut line in parser::lines(&buf) {
+                if start == 0 && offset(&buf, line) == 0 {
+                    line = line.strip_prefix('\u{feff}').unwrap_or(line);
                 }
+                parser.line(line, start + offset(&buf, line));
             }
+            start += buf.len();
+            buf.clear();
         }
-
-        Self { root, sections, cwd: None }
+        Ok(parser.finish().editor_config)
     }
 
     /// Resolve a given path and return the resolved properties.
-    /// If `cwd` is set, absolute paths will be resolved relative to `cwd`.
+    /// If the config path or `cwd` is set, absolute paths will be resolved relative to it.
+    /// On Windows, paths with `\` separators are supported.
     pub fn resolve(&self, path: &Path) -> EditorConfigProperties {
-        let path =
-            if let Some(cwd) = &self.cwd { path.strip_prefix(cwd).unwrap_or(path) } else { path };
-        let mut properties = EditorConfigProperties::default();
-        for section in &self.sections {
-            if section.matcher.as_ref().is_some_and(|matcher| matcher.is_match(path)) {
-                properties.override_with(&section.properties);
+        self.resolve_with_matches(path).properties
+    }
+
+    /// Resolve like [EditorConfig::resolve], also counting the sections that matched,
+    /// to distinguish "no section matched" from "every property was unset".
+    pub fn resolve_with_matches(&self, path: &Path) -> Resolved {
+        self.relative_path(path).map(|path| self.resolve_relative(&path)).unwrap_or_default()
+    }
+
+    /// Resolve a file name without a directory, e.g. for embedded callers that have nothing else.
+    ///
+    /// Only sections whose pattern has no `/` are matched, such as `[*.rs]` or `[Makefile]`;
+    /// `cwd` and the config path are not used. This is a strict subset of
+    /// [EditorConfig::resolve], which gives the same result for a file next to the config file
+    /// when no section pattern has a `/`.
+    pub fn resolve_filename(&self, name: &str) -> EditorConfigProperties {
+        self.resolve_sections(name.as_bytes(), |i| self.is_unanchored(i)).properties
+    }
+
+    /// Resolve the properties that apply to every file directly inside `dir`, whatever its name,
+    /// e.g. to pre-compute per-directory defaults.
+    ///
+    /// As in editorconfig-core when resolving a path with a trailing `/`, the directory is matched
+    /// as `dir/`, i.e. as a file with an empty name inside it:
+    /// `[*]`, `[**]`, `[dir/*]` and `[dir/**]` apply, while `[*.rs]` and `[?]` do not.
+    pub fn resolve_directory(&self, dir: &Path) -> EditorConfigProperties {
+        let Some(dir) = self.relative_path(dir) else {
+            return EditorConfigProperties::default();
+        };
+        let mut dir = dir.into_owned();
+        if !dir.is_empty() {
+            dir.push(b'/');
+        }
+        self.resolve_relative(&dir).properties
+    }
+
+    fn resolve_relative(&self, path: &[u8]) -> Resolved {
+        self.resolve_sections(path, |_| true)
+    }
+
+    /// Apply the sections of this config that `include` accepts and that match `path`, in order.
+    /// Every resolve goes through here, so that the merge cache and the observer see it.
+    fn resolve_sections<F: Fn(usize) -> bool>(&self, path: &[u8], include: F) -> Resolved {
+        let start = self.observer.0.as_ref().map(|_| Instant::now());
+        let index = self.extension_index.get_or_init(|| ExtensionIndex::new(self));
+        let candidate = Candidate::from_bytes(path);
+        let candidates =
+            index.candidates(path).into_iter().filter(|i| include(*i)).collect::<Vec<_>>();
+        let candidate_count = candidates.len();
+        let matched = candidates
+            .into_iter()
+            .filter(|i| {
+                self.sections[*i]
+                    .matcher
+                    .as_ref()
+                    .is_some_and(|matcher| matcher.is_match_candidate(&candidate))
+            })
+            .collect::<Vec<_>>();
+        let (properties, cache_hit) = self.merge_cache.get_or_merge(&matched, || {
+            let mut properties = EditorConfigProperties::default();
+            for i in &matched {
+                properties.override_with(&self.sections[*i].properties);
             }
+            if let Some(version) = self.spec_version {
+                properties.apply_core_defaults(version);
+            }
+            properties
+        });
+        if let (Some(observer), Some(start)) = (&self.observer.0, start) {
+            observer.resolved(&ResolveEvent {
+                path,
+                duration: start.elapsed(),
+                candidates: candidate_count,
+                matched_sections: matched.len(),
+                cache_hit,
+            });
         }
-        properties
+        Resolved { properties, matched_sections: matched.len() }
     }
-}
 
-impl<T: Copy> EditorConfigProperty<T> {
-    fn override_with(&mut self, other: &Self) {
-        match other {
-            Self::Value(value) => {
-                *self = Self::Value(*value);
+    /// Whether the section at `index` has no `/` in its pattern, so it matches by file name.
+    fn is_unanchored(&self, index: usize) -> bool {
+        !self.sections[index].name.contains('/')
+    }
+
+    /// The path that section globs are matched against, as bytes separated by `/`,
+    /// or `None` if it is outside the config file's directory.
+    fn relative_path<'a>(&self, path: &'a Path) -> Option<Cow<'a, [u8]>> {
+        match self.locate(path) {
+            Location::Relative(path) | Location::OutsideCwd(path) => Some(path),
+            Location::OutsideConfigDir => None,
+        }
+    }
+
+    fn locate<'a>(&self, path: &'a Path) -> Location<'a> {
+        let bytes = path::to_bytes(path);
+        let Some(base) = self.config_dir.as_ref().or(self.cwd.as_ref()) else {
+            return Location::Relative(bytes);
+        };
+        let start =
+            path::strip_prefix(&bytes, &path::to_bytes(base)).map(|rest| bytes.len() - rest.len());
+        match (start, bytes) {
+            (Some(start), Cow::Borrowed(bytes)) => {
+                Location::Relative(Cow::Borrowed(&bytes[start..]))
             }
-            Self::Unset => {
-                *self = Self::None;
+            (Some(start), Cow::Owned(mut bytes)) => {
+                bytes.drain(..start);
+                Location::Relative(Cow::Owned(bytes))
             }
-            Self::None => {}
+            // Relative paths are taken to be relative to the base already.
+            (None, bytes) if path.is_relative() => Location::Relative(bytes),
+            (None, bytes) if self.config_dir.is_none() => Location::OutsideCwd(bytes),
+            (None, _) => Location::OutsideConfigDir,
         }
     }
 }
 
-impl EditorConfigProperties {
-    fn override_with(&mut self, other: &Self) {
-        self.indent_style.override_with(&other.indent_style);
-        self.indent_size.override_with(&other.indent_size);
-        self.tab_width.override_with(&other.tab_width);
-        self.end_of_line.override_with(&other.end_of_line);
-        self.charset.override_with(&other.charset);
-        self.trim_trailing_whitespace.override_with(&other.trim_trailing_whitespace);
-        self.insert_final_newline.override_with(&other.insert_final_newline);
-        self.max_line_length.override_with(&other.max_line_length);
-    }
+/// Where a path is relative to the directory that section globs are matched in.
+enum Location<'a> {
+    /// Relative to the config file's directory or `cwd`, as bytes separated by `/`.
+    Relative(Cow<'a, [u8]>),
+    /// An absolute path outside `cwd`, as bytes separated by `/`.
+    OutsideCwd(Cow<'a, [u8]>),
+    /// An absolute path outside the config file's directory, which no section applies to.
+    OutsideConfigDir,
 }
 
-impl EditorConfigProperty<usize> {
-    fn parse(s: &str) -> Self {
-        if s.eq_ignore_ascii_case("unset") {
-            Self::Unset
-        } else {
-            s.parse::<usize>().map_or(Self::None, EditorConfigProperty::Value)
+/// The byte offset of `inner`, a slice of `outer`.
+fn offset(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// The 1-based column of `text`, a slice of `line`, in characters.
+fn column(line: &str, text: &str) -> usize {
+    line[..offset(line, text)].chars().count() + 1
+}
+
+impl EditorConfigSection {
+    fn new(name: &str) -> Self {
+        let (matcher, glob_error) = match EditorConfigGlob::new(name) {
+            Ok(matcher) => (Some(matcher), None),
+            Err(err) => (None, Some(err)),
+        };
+        Self { name: name.to_string(), matcher, glob_error, ..Self::default() }
+    }
+
+    /// Parse a section from its name and the key-value pairs that follow its header.
+    ///
+    /// Blank lines, comments and section headers in `body_text` are ignored.
+    pub fn parse_body(name: &str, body_text: &str) -> Self {
+        let mut section = Self::new(name);
+        for line in parser::lines(body_text) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with([';', '#', '[']) {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                section.set(key.trim_end(), value.trim_start());
+            }
         }
+        section
+    }
+
+    /// The value of the last pair for `key` as written, e.g. `LF` for `end_of_line = LF`,
+    /// where [EditorConfigSection::properties] has the parsed [EndOfLine::Lf].
+    pub fn raw_value(&self, key: PropertyKey) -> Option<&str> {
+        self.raw_properties.iter().rev().find(|(k, _)| k == key.name()).map(|(_, v)| v.as_str())
+    }
+
+    /// Set a known property, or keep an unknown one in [EditorConfigSection::unknown_properties].
+    /// Returns `false` if the key is known but the value could not be parsed.
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        self.set_with_aliases(key, value, &[])
     }
-}
 
-impl EditorConfigProperty<bool> {
-    fn parse(s: &str) -> Self {
-        if s.eq_ignore_ascii_case("true") {
-            EditorConfigProperty::Value(true)
-        } else if s.eq_ignore_ascii_case("false") {
-            EditorConfigProperty::Value(false)
-        } else if s.eq_ignore_ascii_case("unset") {
-            EditorConfigProperty::Unset
+    /// Like [EditorConfigSection::set], with [ParseOptions::max_line_length_off_aliases].
+    fn set_with_aliases(&mut self, key: &str, value: &str, off_aliases: &[String]) -> bool {
+        let key = key.to_ascii_lowercase();
+        if EditorConfigProperties::KEYS.contains(&key.as_str()) {
+            let value_to_parse = if key == "max_line_length"
+                && !cfg!(feature = "spec-strict")
+                && off_aliases.iter().any(|alias| alias.eq_ignore_ascii_case(value))
+            {
+                Cow::Borrowed("off")
+            } else {
+                value::normalize(&key, value)
+            };
+            let valid = self.properties.set(&key, &value_to_parse);
+            self.raw_properties.push((key, value.to_string()));
+            valid
         } else {
-            EditorConfigProperty::None
+            self.properties.set_unknown(&key, value);
+            self.unknown_properties.push((key, value.to_string()));
+            true
+        }
+    }
+
+    /// Whether the section applies to `path`, relative to the config file's directory.
+    /// A section whose glob failed to compile matches nothing.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.matcher.as_ref().is_some_and(|matcher| matcher.is_match(path))
+    }
+
+    /// Like [EditorConfigSection::matches], but fails if the glob failed to compile.
+    ///
+    /// # Errors
+    ///
+    /// Returns [EditorConfigSection::glob_error], or for a section built without a matcher,
+    /// the error compiling the pattern with default options.
+    pub fn try_matches(&self, path: &Path) -> Result<bool, GlobError> {
+        match (&self.matcher, &self.glob_error) {
+            (Some(matcher), _) => Ok(matcher.is_match(path)),
+            (None, Some(err)) => Err(err.clone()),
+            (None, None) => EditorConfigGlob::new(&self.name).map(|_| false),
         }
     }
 }
 
-impl IndentStyle {
-    fn parse(s: &str) -> EditorConfigProperty<Self> {
-        if s.eq_ignore_ascii_case("tab") {
-            EditorConfigProperty::Value(Self::Tab)
-        } else if s.eq_ignore_ascii_case("space") {
-            EditorConfigProperty::Value(Self::Space)
-        } else if s.eq_ignore_ascii_case("unset") {
-            EditorConfigProperty::Unset
-        } else {
-            EditorConfigProperty::None
+impl<T> EditorConfigProperty<T> {
+    /// Whether the property has a value, i.e. it is neither unspecified nor `unset`.
+    pub fn is_set(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    /// The value, if there is one.
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::None | Self::Unset => None,
+        }
+    }
+
+    /// The value, or `default` if there is none.
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            Self::Value(value) => value,
+            Self::None | Self::Unset => default,
+        }
+    }
+
+    /// Map the value with `f`, keeping [EditorConfigProperty::None] and
+    /// [EditorConfigProperty::Unset].
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> EditorConfigProperty<U> {
+        match self {
+            Self::Value(value) => EditorConfigProperty::Value(f(value)),
+            Self::None => EditorConfigProperty::None,
+            Self::Unset => EditorConfigProperty::Unset,
+        }
+    }
+
+    /// `self` if it has a value, otherwise `other`.
+    pub fn or(self, other: Self) -> Self {
+        if self.is_set() { self } else { other }
+    }
+
+    /// The property without losing `unset`: `None` if unspecified, `Some(None)` if `unset`, and
+    /// `Some(Some(value))` otherwise.
+    pub fn into_tristate(self) -> Option<Option<T>> {
+        match self {
+            Self::None => None,
+            Self::Unset => Some(None),
+            Self::Value(value) => Some(Some(value)),
         }
     }
 }
 
-impl EditorConfigProperty<EndOfLine> {
-    fn parse(s: &str) -> Self {
-        if s.eq_ignore_ascii_case("lf") {
-            Self::Value(EndOfLine::Lf)
-        } else if s.eq_ignore_ascii_case("cr") {
-            Self::Value(EndOfLine::Cr)
-        } else if s.eq_ignore_ascii_case("crlf") {
-            Self::Value(EndOfLine::Crlf)
-        } else if s.eq_ignore_ascii_case("unset") {
-            Self::Unset
-        } else {
-            Self::None
+/// The value, if there is one, see [EditorConfigProperty::into_tristate] to keep `unset`.
+impl<T> From<EditorConfigProperty<T>> for Option<T> {
+    fn from(property: EditorConfigProperty<T>) -> Self {
+        match property {
+            EditorConfigProperty::Value(value) => Some(value),
+            EditorConfigProperty::None | EditorConfigProperty::Unset => None,
         }
     }
 }
 
-impl EditorConfigProperty<Charset> {
-    fn parse(s: &str) -> Self {
-        if s.eq_ignore_ascii_case("utf-8") {
-            Self::Value(Charset::Utf8)
-        } else if s.eq_ignore_ascii_case("latin1") {
-            Self::Value(Charset::Latin1)
-        } else if s.eq_ignore_ascii_case("utf-16be") {
-            Self::Value(Charset::Utf16be)
-        } else if s.eq_ignore_ascii_case("utf-16le") {
-            Self::Value(Charset::Utf16le)
-        } else if s.eq_ignore_ascii_case("utf-8-bom") {
-            Self::Value(Charset::Utf8bom)
-        } else if s.eq_ignore_ascii_case("unset") {
-            Self::Unset
-        } else {
-            Self::None
+impl<T: Clone> EditorConfigProperty<T> {
+    fn override_with(&mut self, other: &Self) {
+        match other {
+            Self::Value(value) => {
+                *self = Self::Value(value.clone());
+            }
+            Self::Unset => {
+                *self = Self::None;
+            }
+            Self::None => {}
         }
     }
 }
 
-impl EditorConfigProperty<MaxLineLength> {
-    fn parse(s: &str) -> Self {
-        if s.eq_ignore_ascii_case("off") {
-            Self::Value(MaxLineLength::Off)
-        } else if s.eq_ignore_ascii_case("unset") {
-            Self::Unset
-        } else if let Ok(n) = s.parse::<usize>() {
-            Self::Value(MaxLineLength::Number(n))
-        } else {
-            Self::None
+impl EditorConfigProperties {
+    /// The keys of the properties above, in the order of [PropertyKey::ALL].
+    pub(crate) const KEYS: [&str; 8] = [
+        "indent_style",
+        "indent_size",
+        "tab_width",
+        "end_of_line",
+        "charset",
+        "trim_trailing_whitespace",
+        "insert_final_newline",
+        "max_line_length",
+    ];
+
+    /// Set a property from its key, which is case-insensitive, and value.
+    /// Returns `false` if the key is known but the value could not be parsed.
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        fn assign<T>(field: &mut EditorConfigProperty<T>, value: EditorConfigProperty<T>) -> bool {
+            *field = value;
+            !matches!(field, EditorConfigProperty::None)
+        }
+        match key.to_ascii_lowercase().as_str() {
+            "indent_style" => assign(&mut self.indent_style, value::parse_indent_style(value)),
+            "indent_size" => assign(&mut self.indent_size, value::parse_indent_size(value)),
+            "tab_width" => assign(&mut self.tab_width, value::parse_tab_width(value)),
+            "end_of_line" => assign(&mut self.end_of_line, value::parse_end_of_line(value)),
+            "charset" => assign(&mut self.charset, value::parse_charset(value)),
+            "trim_trailing_whitespace" => {
+                assign(&mut self.trim_trailing_whitespace, value::parse_bool(value))
+            }
+            "insert_final_newline" => {
+                assign(&mut self.insert_final_newline, value::parse_bool(value))
+            }
+            "max_line_length" => {
+                assign(&mut self.max_line_length, value::parse_max_line_length(value))
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether the property `key`, which is lowercase, has a value outside the specification,
+    /// e.g. [EndOfLine::Other].
+    pub(crate) fn is_nonstandard(&self, key: &str) -> bool {
+        match key {
+            "indent_style" => {
+                matches!(self.indent_style, EditorConfigProperty::Value(IndentStyle::Other(_)))
+            }
+            "end_of_line" => {
+                matches!(self.end_of_line, EditorConfigProperty::Value(EndOfLine::Other(_)))
+            }
+            "charset" => matches!(self.charset, EditorConfigProperty::Value(Charset::Other(_))),
+            _ => false,
+        }
+    }
+
+    /// Apply `other` on top of these properties, as for a later matching section or a config
+    /// closer to the file: its values replace these, and its `unset` properties are removed.
+    pub fn override_with(&mut self, other: &Self) {
+        self.indent_style.override_with(&other.indent_style);
+        self.indent_size.override_with(&other.indent_size);
+        self.tab_width.override_with(&other.tab_width);
+        self.end_of_line.override_with(&other.end_of_line);
+        self.charset.override_with(&other.charset);
+        self.trim_trailing_whitespace.override_with(&other.trim_trailing_whitespace);
+        self.insert_final_newline.override_with(&other.insert_final_newline);
+        self.max_line_length.override_with(&other.max_line_length);
+        for (key, value) in &other.unknown {
+            if value.eq_ignore_ascii_case("unset") {
+                self.unknown.retain(|(k, _)| k != key);
+            } else {
+                self.set_unknown(key, value);
+            }
+        }
+    }
+
+    /// Like [EditorConfigProperties::override_with], without changing `self`.
+    pub fn merged(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        merged.override_with(other);
+        merged
+    }
+
+    fn set_unknown(&mut self, key: &str, value: &str) {
+        match self.unknown.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.unknown.push((key.to_string(), value.to_string())),
         }
     }
 }
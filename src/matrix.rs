@@ -0,0 +1,144 @@
+//! The effective settings of representative file types, for publishing as documentation.
+
+use std::fmt::Write as _;
+
+use crate::{EditorConfig, serialize::json_string};
+
+/// Properties resolved for a file with each extension, see [settings_matrix].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct SettingsMatrix {
+    pub rows: Vec<MatrixRow>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatrixRow {
+    /// The extension, without the leading `.`.
+    pub extension: String,
+
+    /// The properties that are set, in the order of
+    /// [EditorConfigProperties](crate::EditorConfigProperties).
+    pub cells: Vec<MatrixCell>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatrixCell {
+    pub key: &'static str,
+
+    /// The value in the `.editorconfig` format.
+    pub value: String,
+
+    /// Index of the config in the chain that set the value.
+    pub config: usize,
+
+    /// Name of the section that set the value.
+    pub section: String,
+}
+
+/// Resolve a file named `file.<extension>` for each extension through `configs`,
+/// recording which section set each property.
+///
+/// `configs` is ordered from the outermost directory to the innermost, so later configs
+/// override earlier ones; configs before the last one with `root = true` are ignored.
+pub fn settings_matrix(configs: &[EditorConfig], extensions: &[&str]) -> SettingsMatrix {
+    let start = configs.iter().rposition(EditorConfig::root).unwrap_or(0);
+    let rows = extensions
+        .iter()
+        .map(|extension| {
+            let path = format!("file.{extension}");
+            let mut cells: Vec<MatrixCell> = vec![];
+            for (config, editor_config) in configs.iter().enumerate().skip(start) {
+                for section in &editor_config.sections {
+                    if !section.matcher.as_ref().is_some_and(|m| m.is_match_bytes(path.as_bytes()))
+                    {
+                        continue;
+                    }
+                    for (key, value) in &section.properties {
+                        let key = key.name();
+                        cells.retain(|cell| cell.key != key);
+                        if value != "unset" {
+                            let section = section.name.clone();
+                            cells.push(MatrixCell { key, value, config, section });
+                        }
+                    }
+                }
+            }
+            cells.sort_by_key(|cell| {
+                crate::EditorConfigProperties::KEYS.iter().position(|key| *key == cell.key)
+            });
+            MatrixRow { extension: (*extension).to_string(), cells }
+        })
+        .collect();
+    SettingsMatrix { rows }
+}
+
+impl SettingsMatrix {
+    /// The keys set for any extension, in the order of [MatrixRow::cells].
+    pub fn keys(&self) -> Vec<&'static str> {
+        crate::EditorConfigProperties::KEYS
+            .into_iter()
+            .filter(|key| self.rows.iter().any(|row| row.get(key).is_some()))
+            .collect()
+    }
+
+    /// A Markdown table with a row per extension and a column per key,
+    /// each value followed by the section that set it.
+    pub fn to_markdown(&self) -> String {
+        let keys = self.keys();
+        let mut out = String::from("| Extension |");
+        for key in &keys {
+            let _ = write!(out, " `{key}` |");
+        }
+        out.push_str("\n|---|");
+        out.push_str(&"---|".repeat(keys.len()));
+        out.push('\n');
+        for row in &self.rows {
+            let _ = write!(out, "| `.{}` |", row.extension);
+            for key in &keys {
+                match row.get(key) {
+                    Some(cell) => {
+                        let _ = write!(out, " `{}` from `[{}]` |", cell.value, cell.section);
+                    }
+                    None => out.push_str(" |"),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A JSON array with an object per extension, such as
+    /// `{"extension":"rs","properties":{"indent_size":{"value":"4","config":0,"section":"*.rs"}}}`.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ =
+                write!(out, "{{\"extension\":{},\"properties\":{{", json_string(&row.extension));
+            for (j, cell) in row.cells.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                let _ = write!(
+                    out,
+                    "\"{}\":{{\"value\":{},\"config\":{},\"section\":{}}}",
+                    cell.key,
+                    json_string(&cell.value),
+                    cell.config,
+                    json_string(&cell.section)
+                );
+            }
+            out.push_str("}}");
+        }
+        out.push(']');
+        out
+    }
+}
+
+impl MatrixRow {
+    /// The cell of `key`, if the property is set.
+    pub fn get(&self, key: &str) -> Option<&MatrixCell> {
+        self.cells.iter().find(|cell| cell.key == key)
+    }
+}
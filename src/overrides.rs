@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use ignore::overrides::{Override, OverrideBuilder};
+
+use crate::{EditorConfig, glob};
+
+impl EditorConfig {
+    /// Build an [Override] that matches every file covered by at least one section,
+    /// for pre-filtering candidates while walking with the `ignore` crate.
+    ///
+    /// Sections whose glob failed to compile are skipped, as they never match. Numeric ranges
+    /// such as `{1..3}` only cover numbers written without a sign or leading zeros.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section glob is rejected by the `ignore` crate.
+    #[cfg_attr(docsrs, doc(cfg(feature = "ignore")))]
+    pub fn to_overrides<P: AsRef<Path>>(&self, root: P) -> Result<Override, ignore::Error> {
+        let mut builder = OverrideBuilder::new(root);
+        for section in &self.sections {
+            if section.matcher.is_none() {
+                continue;
+            }
+            for glob in
+                glob::globs(&section.name, self.glob_limits, self.match_options).unwrap_or_default()
+            {
+                builder.add(&glob)?;
+            }
+        }
+        builder.build()
+    }
+}
@@ -0,0 +1,342 @@
+//! The line-by-line `.editorconfig` parser behind [EditorConfig::parse] and
+//! [EditorConfig::from_reader].
+
+use std::collections::HashMap;
+
+use crate::{
+    Diagnostic, DiagnosticKind, EditorConfig, EditorConfigProperties, EditorConfigSection,
+    GlobLimits, LineKind, LineRecord, PairSpans, ParseError, ParseErrorKind, ParseOptions,
+    SPEC_MAX_KEY_LEN, SPEC_MAX_SECTION_NAME_LEN, SPEC_MAX_VALUE_LEN, SectionSpans, Span, column,
+    glob::GlobInterner, offset,
+};
+
+pub(crate) struct Parsed {
+    pub(crate) editor_config: EditorConfig,
+    /// Problems that make [EditorConfig::try_parse] fail.
+    pub(crate) errors: Vec<ParseError>,
+    /// Problems reported by [EditorConfig::parse_with_diagnostics].
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    /// Empty unless spans are recorded.
+    pub(crate) spans: Vec<SectionSpans>,
+}
+
+pub(crate) struct Parser<'a> {
+    options: &'a ParseOptions,
+    glob_limits: GlobLimits,
+    globs: GlobInterner,
+    root: Option<bool>,
+    /// Whether no section header has been seen yet.
+    in_preamble: bool,
+    preamble: Vec<(String, String)>,
+    sections: Vec<EditorConfigSection>,
+    /// The index of the section that pairs are added to.
+    current: Option<usize>,
+    /// The index and line number of the first section with each name.
+    headers: HashMap<String, (usize, usize)>,
+    /// The line number of the last pair of each lowercased key, by section index.
+    keys: HashMap<(usize, String), usize>,
+    errors: Vec<ParseError>,
+    diagnostics: Vec<Diagnostic>,
+    /// `None` unless spans are recorded.
+    spans: Option<Vec<SectionSpans>>,
+    lines: Vec<LineRecord>,
+    /// The 1-based number of the next line.
+    line_number: usize,
+    /// Whether an [InputLimits](crate::InputLimits) limit was hit, so the rest is ignored.
+    truncated: bool,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(options: &'a ParseOptions, record_spans: bool) -> Self {
+        let mut glob_limits = options.glob_limits;
+        if cfg!(feature = "spec-strict") {
+            glob_limits.max_pattern_len =
+                glob_limits.max_pattern_len.min(SPEC_MAX_SECTION_NAME_LEN);
+        }
+        Self {
+            options,
+            glob_limits,
+            globs: GlobInterner::default(),
+            root: None,
+            in_preamble: true,
+            preamble: vec![],
+            sections: vec![],
+            current: None,
+            headers: HashMap::new(),
+            keys: HashMap::new(),
+            errors: vec![],
+            diagnostics: vec![],
+            spans: record_spans.then(Vec::new),
+            lines: vec![],
+            line_number: 1,
+            truncated: false,
+        }
+    }
+
+    /// Parse the next line, `raw`, without its line ending.
+    /// `start` is the byte offset of the line in the source, for spans.
+    pub(crate) fn line(&mut self, raw: &str, start: usize) {
+        let limits = self.options.input_limits;
+        if self.truncated {
+            return;
+        }
+        if start.saturating_add(raw.len()) > limits.max_input_len {
+            let kind = DiagnosticKind::InputTooLong { max: limits.max_input_len };
+            self.truncate(self.line_number, kind);
+            return;
+        }
+        let kind = if raw.len() > limits.max_line_len {
+            let kind = DiagnosticKind::LineTooLong { max: limits.max_line_len };
+            self.diagnostics.push(Diagnostic { line: self.line_number, kind });
+            self.line_number += 1;
+            // Pairs under a dropped header must not apply to the previous section.
+            if raw.trim_start().starts_with('[') {
+                self.in_preamble = false;
+                self.current = None;
+            }
+            LineKind::Other
+        } else {
+            self.parse_line(raw, start)
+        };
+        if self.truncated {
+            return;
+        }
+        let line = raw.trim();
+        let span = |text: &str| {
+            let start = start + offset(raw, text);
+            Span { start, end: start + text.len() }
+        };
+        let (key, value) = match (kind, self.split_pair(line)) {
+            (LineKind::Pair, Some((key, value))) => (Some(span(key)), Some(span(value))),
+            _ => (None, None),
+        };
+        self.lines.push(LineRecord { kind, span: span(line), key, value, section: self.current });
+    }
+
+    fn parse_line(&mut self, raw: &str, start: usize) -> LineKind {
+        // EditorConfig files are in an INI-like file format.
+        // To read an EditorConfig file, take one line at a time, from beginning to end.
+        // For each line:
+        // 1. Remove all leading and trailing whitespace.
+        // 2. Process the remaining text as specified for its type below.
+        let line_number = self.line_number;
+        self.line_number += 1;
+        let line = raw.trim();
+        let error = |text: &str, kind| ParseError {
+            line: line_number,
+            column: column(raw, text),
+            text: text.to_string(),
+            kind,
+        };
+        let span = |text: &str| {
+            let start = start + offset(raw, text);
+            Span { start, end: start + text.len() }
+        };
+        // Blank: Contains nothing. Blank lines are ignored.
+        if line.is_empty() {
+            return LineKind::Blank;
+        }
+        // Comment: starts with a ; or a #. Comment lines are ignored.
+        // There are no inline comments, so `[foo\#bar.txt]` keeps its escaped `#`.
+        if line.starts_with([';', '#']) {
+            return LineKind::Comment;
+        }
+        // Parse `root`. Must be specified in the preamble. The value is case-insensitive.
+        if self.in_preamble
+            && !line.starts_with('[')
+            && let Some((key, value)) = self.split_pair(line)
+        {
+            if key.eq_ignore_ascii_case("root") {
+                self.root = Some(self.root == Some(true) || value.eq_ignore_ascii_case("true"));
+            } else {
+                let key = key.to_ascii_lowercase();
+                // Custom keys of other tools are expected here, but properties have no effect.
+                if EditorConfigProperties::KEYS.contains(&key.as_str()) {
+                    let kind = DiagnosticKind::NoSection { key: key.clone() };
+                    self.diagnostics.push(Diagnostic { line: line_number, kind });
+                }
+                self.preamble.push((key, value.to_string()));
+            }
+        }
+        // Section Header: starts with a [ and ends with a ]. These lines define globs;
+        if line.starts_with('[') {
+            self.in_preamble = false;
+            if let Some(name) = section_name(line) {
+                if let Some(&(index, first_line)) = self.headers.get(name) {
+                    let kind =
+                        DiagnosticKind::DuplicateSection { name: name.to_string(), first_line };
+                    self.diagnostics.push(Diagnostic { line: line_number, kind });
+                    if self.options.merge_duplicate_sections {
+                        self.current = Some(index);
+                        return LineKind::Header;
+                    }
+                }
+                // Repeated headers count too, as each starts a new section.
+                if self.sections.len() == self.options.input_limits.max_sections {
+                    let max = self.options.input_limits.max_sections;
+                    self.truncate(line_number, DiagnosticKind::TooManySections { max });
+                    return LineKind::Other;
+                }
+                self.headers.entry(name.to_string()).or_insert((self.sections.len(), line_number));
+                let compiled =
+                    self.globs.compile(name, false, self.glob_limits, self.options.match_options);
+                let (matcher, glob_error) = match compiled {
+                    Ok(matcher) => (Some(matcher), None),
+                    Err(err) => {
+                        let reason = err.kind.to_string();
+                        let kind =
+                            DiagnosticKind::InvalidGlob { pattern: err.pattern.clone(), reason };
+                        self.diagnostics.push(Diagnostic { line: line_number, kind });
+                        let kind = ParseErrorKind::InvalidGlob { reason: err.kind.to_string() };
+                        self.errors.push(error(name, kind));
+                        (None, Some(err))
+                    }
+                };
+                if let Some(spans) = &mut self.spans {
+                    spans.push(SectionSpans {
+                        header: span(&line[..name.len() + 2]),
+                        name: span(name),
+                        pairs: vec![],
+                    });
+                }
+                self.current = Some(self.sections.len());
+                self.sections.push(EditorConfigSection {
+                    name: name.to_string(),
+                    matcher,
+                    glob_error,
+                    ..EditorConfigSection::default()
+                });
+                return LineKind::Header;
+            }
+            // Ignored when parsing leniently, an error for `try_parse`.
+            let kind = DiagnosticKind::InvalidHeader { header: line.to_string() };
+            self.diagnostics.push(Diagnostic { line: line_number, kind });
+            self.errors.push(error(line, ParseErrorKind::InvalidHeader));
+            return LineKind::Other;
+        }
+        // Key-Value Pair (or Pair): contains a key and a value, separated by an `=`.
+        if let Some(current) = self.current
+            && let Some((key, value)) = self.split_pair(line)
+        {
+            // Longer pairs are ignored.
+            let too_long_chars = cfg!(feature = "spec-strict")
+                && (key.chars().count() > SPEC_MAX_KEY_LEN
+                    || value.chars().count() > SPEC_MAX_VALUE_LEN);
+            let too_long_bytes = self.options.core_length_limits
+                && (key.len() > SPEC_MAX_KEY_LEN || value.len() > SPEC_MAX_VALUE_LEN);
+            if too_long_chars || too_long_bytes {
+                return LineKind::Pair;
+            }
+            let lowercase = (current, key.to_ascii_lowercase());
+            if let Some(previous_line) = self.keys.insert(lowercase, line_number) {
+                let kind =
+                    DiagnosticKind::DuplicateKey { key: key.to_ascii_lowercase(), previous_line };
+                self.diagnostics.push(Diagnostic { line: line_number, kind });
+            }
+            if let Some(spans) = &mut self.spans {
+                spans[current].pairs.push(PairSpans { key: span(key), value: span(value) });
+            }
+            let off_aliases = &self.options.max_line_length_off_aliases;
+            let valid = self.sections[current].set_with_aliases(key, value, off_aliases);
+            let lowercase_key = key.to_ascii_lowercase();
+            let kind = if !valid {
+                Some(DiagnosticKind::InvalidValue { key: lowercase_key, value: value.to_string() })
+            } else if self.sections[current].properties.is_nonstandard(&lowercase_key) {
+                // Kept as `Other`, but still a problem for `try_parse`.
+                Some(DiagnosticKind::NonstandardValue {
+                    key: lowercase_key,
+                    value: value.to_string(),
+                })
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                self.diagnostics.push(Diagnostic { line: line_number, kind });
+                let kind = ParseErrorKind::InvalidValue { key: key.to_string() };
+                self.errors.push(error(value, kind));
+            }
+        }
+        if line.contains('=') { LineKind::Pair } else { LineKind::Other }
+    }
+
+    pub(crate) fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Ignore `line` and every line after it.
+    fn truncate(&mut self, line: usize, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic { line, kind });
+        self.truncated = true;
+    }
+
+    /// The key and value of a pair, without the whitespace around the `=`,
+    /// nor an inline comment with [ParseOptions::inline_comments].
+    fn split_pair<'b>(&self, line: &'b str) -> Option<(&'b str, &'b str)> {
+        let (key, mut value) = line.split_once('=')?;
+        if self.options.inline_comments && !cfg!(feature = "spec-strict") {
+            let comment = value.char_indices().find(|(i, c)| {
+                matches!(c, ';' | '#') && value[..*i].ends_with(char::is_whitespace)
+            });
+            if let Some((i, _)) = comment {
+                value = value[..i].trim_end();
+            }
+        }
+        Some((key.trim_end(), value.trim_start()))
+    }
+
+    pub(crate) fn finish(self) -> Parsed {
+        let editor_config = EditorConfig {
+            root: self.root,
+            sections: self.sections,
+            preamble: self.preamble,
+            glob_limits: self.glob_limits,
+            match_options: self.options.match_options,
+            spec_version: self.options.spec_version,
+            core_length_limits: self.options.core_length_limits,
+            merge_duplicate_sections: self.options.merge_duplicate_sections,
+            inline_comments: self.options.inline_comments,
+            input_limits: self.options.input_limits,
+            max_line_length_off_aliases: self.options.max_line_length_off_aliases.clone(),
+            lines: self.lines,
+            ..EditorConfig::default()
+        };
+        Parsed {
+            editor_config,
+            errors: self.errors,
+            diagnostics: self.diagnostics,
+            spans: self.spans.unwrap_or_default(),
+        }
+    }
+}
+
+/// The name of a trimmed section header `line`, like editorconfig-core: the text between the
+/// leading `[` and the last `]`, so `[a]b]` is named `a]b` and the rest of `[*.md] ; comment`
+/// is ignored.
+pub(crate) fn section_name(line: &str) -> Option<&str> {
+    line.strip_prefix('[')?.rsplit_once(']').map(|(name, _)| name)
+}
+
+/// Split `text` after each `\n`, `\r\n` or lone `\r`, as old Mac editors end lines,
+/// keeping the line endings.
+pub(crate) fn lines_inclusive(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .find(['\n', '\r'])
+            .map_or(rest.len(), |i| if rest[i..].starts_with("\r\n") { i + 2 } else { i + 1 });
+        let (line, tail) = rest.split_at(end);
+        rest = tail;
+        Some(line)
+    })
+}
+
+/// Split `text` into lines like [str::lines], also ending lines at a lone `\r`.
+pub(crate) fn lines(text: &str) -> impl Iterator<Item = &str> {
+    lines_inclusive(text).map(|line| {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        line.strip_suffix('\r').unwrap_or(line)
+    })
+}
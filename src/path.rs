@@ -0,0 +1,50 @@
+//! Paths as matched by section globs: bytes separated by `/`,
+//! so that paths which are not valid UTF-8 still match.
+
+use std::{borrow::Cow, path::Path};
+
+/// The bytes of `path`, with `\` separators normalized to `/` on Windows.
+/// Elsewhere `\` is a valid file name character, which is kept.
+pub(crate) fn to_bytes(path: &Path) -> Cow<'_, [u8]> {
+    let bytes = os_bytes(path);
+    if cfg!(windows) && bytes.contains(&b'\\') {
+        Cow::Owned(bytes.iter().map(|&b| if b == b'\\' { b'/' } else { b }).collect())
+    } else {
+        bytes
+    }
+}
+
+#[cfg(unix)]
+fn os_bytes(path: &Path) -> Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(path.as_os_str().as_bytes())
+}
+
+#[cfg(not(unix))]
+fn os_bytes(path: &Path) -> Cow<'_, [u8]> {
+    // There is no lossless byte representation; unpaired surrogates are replaced.
+    match path.to_string_lossy() {
+        Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+    }
+}
+
+/// Strip the directory `prefix` from `path`, comparing whole components.
+pub(crate) fn strip_prefix<'a>(path: &'a [u8], mut prefix: &[u8]) -> Option<&'a [u8]> {
+    if prefix.is_empty() {
+        return Some(path);
+    }
+    while prefix.len() > 1
+        && let Some(trimmed) = prefix.strip_suffix(b"/")
+    {
+        prefix = trimmed;
+    }
+    let mut rest = path.strip_prefix(prefix)?;
+    if !prefix.ends_with(b"/") && !rest.is_empty() && !rest.starts_with(b"/") {
+        return None;
+    }
+    while let Some(trimmed) = rest.strip_prefix(b"/") {
+        rest = trimmed;
+    }
+    Some(rest)
+}
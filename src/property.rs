@@ -0,0 +1,182 @@
+use std::{fmt, path::Path, str::FromStr};
+
+use crate::{
+    Charset, EditorConfig, EditorConfigProperties, EditorConfigProperty, EndOfLine, IndentSize,
+    IndentStyle, MaxLineLength,
+};
+
+/// A property of [EditorConfigProperties], for asking for a single one.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PropertyKey {
+    IndentStyle,
+    IndentSize,
+    TabWidth,
+    EndOfLine,
+    Charset,
+    TrimTrailingWhitespace,
+    InsertFinalNewline,
+    MaxLineLength,
+}
+
+/// The values a property accepts besides `unset`, see [PropertyKey::syntax].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ValueSyntax {
+    /// One of these keywords, case-insensitive.
+    Keywords(&'static [&'static str]),
+    /// A whole number, or one of these keywords.
+    Number { keywords: &'static [&'static str] },
+}
+
+impl PropertyKey {
+    /// Every property, in the order of the fields of [EditorConfigProperties].
+    pub const ALL: [Self; 8] = [
+        Self::IndentStyle,
+        Self::IndentSize,
+        Self::TabWidth,
+        Self::EndOfLine,
+        Self::Charset,
+        Self::TrimTrailingWhitespace,
+        Self::InsertFinalNewline,
+        Self::MaxLineLength,
+    ];
+
+    /// The key as written in `.editorconfig` files, e.g. `indent_style`.
+    pub fn name(self) -> &'static str {
+        EditorConfigProperties::KEYS[self as usize]
+    }
+
+    /// Same as [PropertyKey::name].
+    pub fn as_str(self) -> &'static str {
+        self.name()
+    }
+
+    /// The values the property accepts besides `unset`.
+    pub fn syntax(self) -> ValueSyntax {
+        const BOOL: &[&str] = &["true", "false"];
+        match self {
+            Self::IndentStyle => ValueSyntax::Keywords(&["tab", "space"]),
+            Self::IndentSize => ValueSyntax::Number { keywords: &["tab"] },
+            Self::TabWidth => ValueSyntax::Number { keywords: &[] },
+            Self::EndOfLine => ValueSyntax::Keywords(&["lf", "cr", "crlf"]),
+            Self::Charset => {
+                ValueSyntax::Keywords(&["latin1", "utf-8", "utf-8-bom", "utf-16be", "utf-16le"])
+            }
+            Self::TrimTrailingWhitespace | Self::InsertFinalNewline => ValueSyntax::Keywords(BOOL),
+            Self::MaxLineLength => ValueSyntax::Number { keywords: &["off"] },
+        }
+    }
+}
+
+impl fmt::Display for PropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A key that is not a [PropertyKey], see its [FromStr] implementation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnknownPropertyKey {
+    pub key: String,
+}
+
+impl fmt::Display for UnknownPropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown property `{}`", self.key)
+    }
+}
+
+impl std::error::Error for UnknownPropertyKey {}
+
+/// Parse a key as written in `.editorconfig` files, case-insensitive.
+impl FromStr for PropertyKey {
+    type Err = UnknownPropertyKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|key| key.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownPropertyKey { key: s.to_string() })
+    }
+}
+
+/// Every property with the values it accepts, for generating shell completions and editor
+/// snippets. Each line is the key, a tab, and the space-separated values, with numbers written
+/// as `<number>`, e.g. `indent_size<TAB><number> unset`.
+pub fn completions() -> String {
+    let mut out = String::new();
+    for key in PropertyKey::ALL {
+        let (number, keywords) = match key.syntax() {
+            ValueSyntax::Keywords(keywords) => (false, keywords),
+            ValueSyntax::Number { keywords } => (true, keywords),
+        };
+        out.push_str(key.name());
+        out.push('\t');
+        if number {
+            out.push_str("<number> ");
+        }
+        for keyword in keywords {
+            out.push_str(keyword);
+            out.push(' ');
+        }
+        out.push_str("unset\n");
+    }
+    out
+}
+
+/// The value of a property, see [PropertyKey].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PropertyValue {
+    IndentStyle(IndentStyle),
+    IndentSize(IndentSize),
+    TabWidth(usize),
+    EndOfLine(EndOfLine),
+    Charset(Charset),
+    TrimTrailingWhitespace(bool),
+    InsertFinalNewline(bool),
+    MaxLineLength(MaxLineLength),
+}
+
+impl EditorConfigProperties {
+    /// The property of `key`.
+    pub fn get(&self, key: PropertyKey) -> EditorConfigProperty<PropertyValue> {
+        fn map<T: Clone>(
+            property: &EditorConfigProperty<T>,
+            f: fn(T) -> PropertyValue,
+        ) -> EditorConfigProperty<PropertyValue> {
+            property.clone().map(f)
+        }
+        match key {
+            PropertyKey::IndentStyle => map(&self.indent_style, PropertyValue::IndentStyle),
+            PropertyKey::IndentSize => map(&self.indent_size, PropertyValue::IndentSize),
+            PropertyKey::TabWidth => map(&self.tab_width, PropertyValue::TabWidth),
+            PropertyKey::EndOfLine => map(&self.end_of_line, PropertyValue::EndOfLine),
+            PropertyKey::Charset => map(&self.charset, PropertyValue::Charset),
+            PropertyKey::TrimTrailingWhitespace => {
+                map(&self.trim_trailing_whitespace, PropertyValue::TrimTrailingWhitespace)
+            }
+            PropertyKey::InsertFinalNewline => {
+                map(&self.insert_final_newline, PropertyValue::InsertFinalNewline)
+            }
+            PropertyKey::MaxLineLength => map(&self.max_line_length, PropertyValue::MaxLineLength),
+        }
+    }
+
+    /// The value of a property that is not a field, e.g. `ij_continuation_indent_size`, as
+    /// written. `key` is case-insensitive.
+    pub fn get_raw(&self, key: &str) -> Option<&str> {
+        let key = key.to_ascii_lowercase();
+        self.unknown.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+impl EditorConfig {
+    /// Resolve a single property for `path`, the same as `resolve(path)` followed by
+    /// [EditorConfigProperties::get].
+    pub fn resolve_property(
+        &self,
+        path: &Path,
+        key: PropertyKey,
+    ) -> EditorConfigProperty<PropertyValue> {
+        self.resolve(path).get(key)
+    }
+}
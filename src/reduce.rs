@@ -0,0 +1,58 @@
+//! Shrink an `.editorconfig` file that triggers a bug, for triaging reports with huge real-world
+//! configs.
+
+use crate::parser::{lines_inclusive, section_name};
+
+/// Remove sections and lines from `source_text` while `reproduces` stays true, returning the
+/// smallest config found.
+///
+/// Whole sections are removed first, then chunks of lines of halving size down to single lines,
+/// repeated until nothing more can be removed. The result depends only on `source_text` and
+/// the answers of `reproduces`, which is called with every candidate. Line endings are kept.
+///
+/// Returns `None`, after a single call, if `reproduces` is false for `source_text` itself.
+pub fn reduce<F>(source_text: &str, mut reproduces: F) -> Option<String>
+where
+    F: FnMut(&str) -> bool,
+{
+    if !reproduces(source_text) {
+        return None;
+    }
+    let mut lines: Vec<&str> = lines_inclusive(source_text).collect();
+    loop {
+        let len = lines.len();
+        // Sections, from the last, so that removing one keeps the ranges before it.
+        for range in sections(&lines).into_iter().rev() {
+            let candidate = [&lines[..range.start], &lines[range.end..]].concat();
+            if reproduces(&candidate.concat()) {
+                lines = candidate;
+            }
+        }
+        let mut chunk = lines.len().div_ceil(2);
+        while chunk > 0 {
+            let mut start = 0;
+            while start < lines.len() {
+                let end = (start + chunk).min(lines.len());
+                let candidate = [&lines[..start], &lines[end..]].concat();
+                if reproduces(&candidate.concat()) {
+                    lines = candidate;
+                } else {
+                    start = end;
+                }
+            }
+            chunk /= 2;
+        }
+        if lines.len() == len {
+            return Some(lines.concat());
+        }
+    }
+}
+
+/// The line ranges of each section, from its header up to the next header.
+fn sections(lines: &[&str]) -> Vec<std::ops::Range<usize>> {
+    let headers: Vec<usize> = (0..lines.len())
+        .filter(|i| section_name(lines[*i].trim()).is_some())
+        .chain([lines.len()])
+        .collect();
+    headers.windows(2).map(|range| range[0]..range[1]).collect()
+}
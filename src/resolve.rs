@@ -0,0 +1,87 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    EditorConfig, Location, Resolved,
+    diagnostic::{self, Message},
+};
+
+/// Options for [EditorConfig::resolve_with_options].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ResolveOptions {
+    pub outside_cwd: OutsideCwd,
+}
+
+/// How to resolve an absolute path outside the `cwd` set with [EditorConfig::with_cwd].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum OutsideCwd {
+    /// Match the absolute path as-is, as [EditorConfig::resolve] does.
+    #[default]
+    MatchAsIs,
+    /// Only apply sections without a `/`, which match file names in any directory,
+    /// and skip sections anchored to the `cwd` such as `[src/**]`.
+    SkipAnchoredSections,
+    /// Return [ResolveError::OutsideCwd].
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ResolveError {
+    /// The path is outside the `cwd`, with [OutsideCwd::Error].
+    OutsideCwd { path: PathBuf, cwd: PathBuf },
+}
+
+impl Message for ResolveError {
+    fn id(&self) -> &'static str {
+        match self {
+            Self::OutsideCwd { .. } => "outside-cwd",
+        }
+    }
+
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::OutsideCwd { path, cwd } => {
+                vec![("path", path.display().to_string()), ("cwd", cwd.display().to_string())]
+            }
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&diagnostic::render(self, diagnostic::english))
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+impl EditorConfig {
+    /// Resolve like [EditorConfig::resolve_with_matches], choosing how to resolve paths outside
+    /// the `cwd`. Paths outside the config file's directory still match no section.
+    ///
+    /// # Errors
+    ///
+    /// Returns [ResolveError::OutsideCwd] for a path outside the `cwd` with [OutsideCwd::Error].
+    pub fn resolve_with_options(
+        &self,
+        path: &Path,
+        options: &ResolveOptions,
+    ) -> Result<Resolved, ResolveError> {
+        match self.locate(path) {
+            Location::Relative(relative) => Ok(self.resolve_relative(&relative)),
+            Location::OutsideConfigDir => Ok(Resolved::default()),
+            Location::OutsideCwd(absolute) => match options.outside_cwd {
+                OutsideCwd::MatchAsIs => Ok(self.resolve_relative(&absolute)),
+                OutsideCwd::SkipAnchoredSections => {
+                    Ok(self.resolve_sections(&absolute, |i| self.is_unanchored(i)))
+                }
+                OutsideCwd::Error => Err(ResolveError::OutsideCwd {
+                    path: path.to_path_buf(),
+                    cwd: self.cwd.clone().unwrap_or_default(),
+                }),
+            },
+        }
+    }
+}
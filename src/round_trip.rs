@@ -0,0 +1,123 @@
+use std::fmt;
+
+use crate::{EditorConfig, EditorConfigProperties, PropertyKey};
+
+/// The first difference between a parsed `.editorconfig` and its reparsed serialization,
+/// see [EditorConfig::round_trips].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RoundTripDivergence {
+    /// The line of the diverging section header in the source, or of the first section header
+    /// for the preamble, if there is one.
+    pub source_line: Option<usize>,
+
+    /// The line of the diverging section header in the serialization, like `source_line`.
+    pub serialized_line: Option<usize>,
+
+    pub kind: RoundTripDivergenceKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RoundTripDivergenceKind {
+    /// `root` differs.
+    Root,
+    /// The number of sections differs.
+    SectionCount { source: usize, serialized: usize },
+    /// A section name differs.
+    SectionName { source: String, serialized: String },
+    /// A property of a section differs.
+    Property { section: String, key: &'static str },
+}
+
+impl fmt::Display for RoundTripDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |line: Option<usize>| line.map_or("end".to_string(), |line| line.to_string());
+        write!(
+            f,
+            "source line {}, serialized line {}: ",
+            line(self.source_line),
+            line(self.serialized_line)
+        )?;
+        match &self.kind {
+            RoundTripDivergenceKind::Root => f.write_str("`root` differs"),
+            RoundTripDivergenceKind::SectionCount { source, serialized } => {
+                write!(f, "{source} sections became {serialized}")
+            }
+            RoundTripDivergenceKind::SectionName { source, serialized } => {
+                write!(f, "section `[{source}]` became `[{serialized}]`")
+            }
+            RoundTripDivergenceKind::Property { section, key } => {
+                write!(f, "`{key}` differs in section `[{section}]`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RoundTripDivergence {}
+
+impl EditorConfig {
+    /// Check that parsing `source_text`, serializing it and parsing the result gives the same
+    /// root flag, sections and properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first divergence.
+    pub fn round_trips(source_text: &str) -> Result<(), RoundTripDivergence> {
+        let source = Self::parse(source_text);
+        let serialized_text = source.to_string();
+        let serialized = Self::parse(&serialized_text);
+        let source_lines = header_lines(source_text);
+        let serialized_lines = header_lines(&serialized_text);
+        let divergence = |i: usize, kind| RoundTripDivergence {
+            source_line: source_lines.get(i).copied(),
+            serialized_line: serialized_lines.get(i).copied(),
+            kind,
+        };
+        if source.root != serialized.root {
+            return Err(divergence(0, RoundTripDivergenceKind::Root));
+        }
+        for (i, (a, b)) in source.sections.iter().zip(&serialized.sections).enumerate() {
+            if a.name != b.name {
+                return Err(divergence(
+                    i,
+                    RoundTripDivergenceKind::SectionName {
+                        source: a.name.clone(),
+                        serialized: b.name.clone(),
+                    },
+                ));
+            }
+            let (a_pairs, b_pairs) = (a.properties.pairs(), b.properties.pairs());
+            let value = |pairs: &[(PropertyKey, String)], key: &str| {
+                pairs.iter().find(|(k, _)| k.name() == key).map(|(_, value)| value.clone())
+            };
+            if let Some(key) = EditorConfigProperties::KEYS
+                .into_iter()
+                .find(|key| value(&a_pairs, key) != value(&b_pairs, key))
+            {
+                return Err(divergence(
+                    i,
+                    RoundTripDivergenceKind::Property { section: a.name.clone(), key },
+                ));
+            }
+        }
+        let (count, serialized_count) = (source.sections.len(), serialized.sections.len());
+        if count != serialized_count {
+            return Err(divergence(
+                count.min(serialized_count),
+                RoundTripDivergenceKind::SectionCount {
+                    source: count,
+                    serialized: serialized_count,
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The 1-based line numbers of section headers, as recognized by [EditorConfig::parse].
+fn header_lines(source_text: &str) -> Vec<usize> {
+    crate::parser::lines(source_text)
+        .enumerate()
+        .filter(|(_, line)| crate::parser::section_name(line.trim()).is_some())
+        .map(|(index, _)| index + 1)
+        .collect()
+}
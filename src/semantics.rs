@@ -0,0 +1,128 @@
+//! A machine-readable description of the resolution rules, so that caches of resolved
+//! properties can be invalidated when they change.
+//!
+//! The rules are:
+//! - sections apply in file order, so a pair in a later matching section overrides the same key
+//!   in an earlier one, and `unset` removes a value set earlier;
+//! - a section name without a `/` matches the file name in any directory, and one with a `/` is
+//!   anchored to the config file's directory, as described by [MatchOptions] and [GlobLimits].
+//!
+//! They cover a single config: finding the `.editorconfig` files that apply to a path, and
+//! combining them up to the one with `root = true`, are left to the caller.
+
+use std::fmt::Write as _;
+
+use crate::{
+    EditorConfig, GlobLimits, InputLimits, MatchOptions, SpecVersion, serialize::json_string,
+};
+
+/// The version of the resolution rules, incremented in any release where resolving the same
+/// config and path may give different properties, e.g. after a change to glob matching or value
+/// parsing.
+pub const fn semantics_version() -> u32 {
+    2
+}
+
+/// The resolution rules in effect for an [EditorConfig], see [EditorConfig::semantics].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Semantics {
+    /// See [semantics_version].
+    pub version: u32,
+
+    /// `globset`, or `builtin` without the `globset` feature.
+    pub glob_backend: &'static str,
+
+    /// Whether the `spec-strict` feature is enabled.
+    pub spec_strict: bool,
+
+    /// Whether section globs match paths case-insensitively.
+    pub case_insensitive: bool,
+
+    pub match_options: MatchOptions,
+
+    pub glob_limits: GlobLimits,
+
+    /// See [ParseOptions::spec_version](crate::ParseOptions::spec_version).
+    pub spec_version: Option<SpecVersion>,
+
+    /// See [ParseOptions::core_length_limits](crate::ParseOptions::core_length_limits).
+    pub core_length_limits: bool,
+
+    /// See [ParseOptions::merge_duplicate_sections](crate::ParseOptions::merge_duplicate_sections).
+    pub merge_duplicate_sections: bool,
+
+    /// See [ParseOptions::inline_comments](crate::ParseOptions::inline_comments).
+    pub inline_comments: bool,
+
+    /// See [ParseOptions::input_limits](crate::ParseOptions::input_limits).
+    pub input_limits: InputLimits,
+
+    /// See [ParseOptions::max_line_length_off_aliases](crate::ParseOptions::max_line_length_off_aliases).
+    pub max_line_length_off_aliases: Vec<String>,
+}
+
+impl Semantics {
+    /// A JSON object with a field per field of [Semantics], e.g.
+    /// `{"version":2,"glob_backend":"globset",...,"max_line_length_off_aliases":[]}`.
+    pub fn to_json(&self) -> String {
+        let MatchOptions { literal_separator, backslash_escape, empty_alternates, posix_classes } =
+            self.match_options;
+        let GlobLimits { max_pattern_len, max_expansions } = self.glob_limits;
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{{\"version\":{},\"glob_backend\":\"{}\",\"spec_strict\":{},\"case_insensitive\":{},",
+            self.version, self.glob_backend, self.spec_strict, self.case_insensitive
+        );
+        let _ = write!(
+            out,
+            "\"match_options\":{{\"literal_separator\":{literal_separator},\
+             \"backslash_escape\":{backslash_escape},\"empty_alternates\":{empty_alternates},\
+             \"posix_classes\":{posix_classes}}},"
+        );
+        let _ = write!(
+            out,
+            "\"glob_limits\":{{\"max_pattern_len\":{max_pattern_len},\
+             \"max_expansions\":{max_expansions}}},"
+        );
+        match self.spec_version {
+            Some(version) => {
+                let _ = write!(out, "\"spec_version\":\"{version}\",");
+            }
+            None => out.push_str("\"spec_version\":null,"),
+        }
+        let InputLimits { max_input_len, max_sections, max_line_len } = self.input_limits;
+        let _ = write!(
+            out,
+            "\"core_length_limits\":{},\"merge_duplicate_sections\":{},\"inline_comments\":{},\
+             \"input_limits\":{{\"max_input_len\":{max_input_len},\"max_sections\":{max_sections},\
+             \"max_line_len\":{max_line_len}}},",
+            self.core_length_limits, self.merge_duplicate_sections, self.inline_comments
+        );
+        let aliases: Vec<String> =
+            self.max_line_length_off_aliases.iter().map(|alias| json_string(alias)).collect();
+        let _ = write!(out, "\"max_line_length_off_aliases\":[{}]}}", aliases.join(","));
+        out
+    }
+}
+
+impl EditorConfig {
+    /// The resolution rules in effect for this config, including the enabled options.
+    /// Two configs with the same source text and equal semantics resolve every path the same.
+    pub fn semantics(&self) -> Semantics {
+        Semantics {
+            version: semantics_version(),
+            glob_backend: if cfg!(feature = "globset") { "globset" } else { "builtin" },
+            spec_strict: cfg!(feature = "spec-strict"),
+            case_insensitive: self.case_insensitive,
+            match_options: self.match_options,
+            glob_limits: self.glob_limits,
+            spec_version: self.spec_version,
+            core_length_limits: self.core_length_limits,
+            merge_duplicate_sections: self.merge_duplicate_sections,
+            inline_comments: self.inline_comments,
+            input_limits: self.input_limits,
+            max_line_length_off_aliases: self.max_line_length_off_aliases.clone(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Serialization back into the `.editorconfig` format.
+//!
+//! Unknown properties are written after the known ones of their section, and
+//! [EditorConfig::preamble] pairs before the first section. Comments, blank lines and invalid values are not preserved.
+
+use std::fmt::{self, Write as _};
+
+use crate::{
+    Charset, EditorConfig, EditorConfigProperties, EditorConfigProperty, EndOfLine, IndentSize,
+    IndentStyle, MaxLineLength, PropertyKey,
+};
+
+impl fmt::Display for IndentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tab => "tab",
+            Self::Space => "space",
+            Self::Other(value) => value,
+        })
+    }
+}
+
+impl fmt::Display for EndOfLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Lf => "lf",
+            Self::Cr => "cr",
+            Self::Crlf => "crlf",
+            Self::Other(value) => value,
+        })
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Latin1 => "latin1",
+            Self::Utf8 => "utf-8",
+            Self::Utf8bom => "utf-8-bom",
+            Self::Utf16be => "utf-16be",
+            Self::Utf16le => "utf-16le",
+            Self::Other(value) => value,
+        })
+    }
+}
+
+impl fmt::Display for IndentSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Tab => f.write_str("tab"),
+        }
+    }
+}
+
+impl fmt::Display for MaxLineLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Off => f.write_str("off"),
+        }
+    }
+}
+
+impl EditorConfigProperties {
+    /// The properties that are set, as keys and values in the `.editorconfig` format, in the
+    /// order of [PropertyKey::ALL], like the output of the editorconfig CLI, e.g.
+    /// `(PropertyKey::IndentSize, "tab")`. `unset` is only found in section properties, as
+    /// resolving removes the property.
+    ///
+    /// Unknown properties are not included, see [EditorConfigProperties::unknown].
+    pub fn iter(&self) -> std::vec::IntoIter<(PropertyKey, String)> {
+        self.pairs().into_iter()
+    }
+
+    /// Like [EditorConfigProperties::iter], without unknown properties.
+    pub(crate) fn pairs(&self) -> Vec<(PropertyKey, String)> {
+        fn pair<T: fmt::Display>(
+            key: PropertyKey,
+            property: &EditorConfigProperty<T>,
+        ) -> Option<(PropertyKey, String)> {
+            match property {
+                EditorConfigProperty::None => None,
+                EditorConfigProperty::Unset => Some((key, "unset".to_string())),
+                EditorConfigProperty::Value(value) => Some((key, value.to_string())),
+            }
+        }
+        [
+            pair(PropertyKey::IndentStyle, &self.indent_style),
+            pair(PropertyKey::IndentSize, &self.indent_size),
+            pair(PropertyKey::TabWidth, &self.tab_width),
+            pair(PropertyKey::EndOfLine, &self.end_of_line),
+            pair(PropertyKey::Charset, &self.charset),
+            pair(PropertyKey::TrimTrailingWhitespace, &self.trim_trailing_whitespace),
+            pair(PropertyKey::InsertFinalNewline, &self.insert_final_newline),
+            pair(PropertyKey::MaxLineLength, &self.max_line_length),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl IntoIterator for &EditorConfigProperties {
+    type Item = (PropertyKey, String);
+    type IntoIter = std::vec::IntoIter<(PropertyKey, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serialize into the `.editorconfig` format, with a blank line between sections.
+impl fmt::Display for EditorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        if let Some(root) = self.root {
+            writeln!(f, "root = {root}")?;
+            separator = "\n";
+        }
+        for (key, value) in &self.preamble {
+            writeln!(f, "{key} = {value}")?;
+            separator = "\n";
+        }
+        for section in &self.sections {
+            writeln!(f, "{separator}[{}]", section.name)?;
+            for (key, value) in &section.properties {
+                writeln!(f, "{key} = {value}")?;
+            }
+            for (key, value) in &section.unknown_properties {
+                writeln!(f, "{key} = {value}")?;
+            }
+            separator = "\n";
+        }
+        Ok(())
+    }
+}
+
+/// `s` as a JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,127 @@
+use std::ops::Range;
+
+use crate::{EditorConfig, ParseOptions};
+
+/// A range of byte offsets into the source text.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The span as a range, for slicing or splicing the source text.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Source locations of a section, see [EditorConfig::parse_with_spans].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct SectionSpans {
+    /// The header from `[` to the last `]`, without any text after it.
+    pub header: Span,
+
+    /// The section name between `[` and `]`.
+    pub name: Span,
+
+    /// Key-value pairs in source order, including unknown keys and invalid values.
+    pub pairs: Vec<PairSpans>,
+}
+
+impl SectionSpans {
+    /// From the start of the header to the end of the last pair.
+    pub fn source_range(&self) -> Range<usize> {
+        let end = self.pairs.last().map_or(self.header.end, |pair| pair.value.end);
+        self.header.start..end
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct PairSpans {
+    pub key: Span,
+    pub value: Span,
+}
+
+impl PairSpans {
+    /// From the start of the key to the end of the value.
+    pub fn source_range(&self) -> Range<usize> {
+        self.key.start..self.value.end
+    }
+}
+
+/// A classified line of the source, see [EditorConfig::lines].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LineRecord {
+    pub kind: LineKind,
+
+    /// The line without leading and trailing whitespace.
+    pub span: Span,
+
+    /// The key of a [LineKind::Pair].
+    pub key: Option<Span>,
+
+    /// The value of a [LineKind::Pair].
+    pub value: Option<Span>,
+
+    /// The index in [EditorConfig::sections] of the section the line is in, or of the section
+    /// it starts for a [LineKind::Header]. `None` in the preamble, and after a header that
+    /// exceeds [InputLimits::max_line_len](crate::InputLimits::max_line_len).
+    pub section: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LineKind {
+    Blank,
+    /// A line starting with `#` or `;`.
+    Comment,
+    /// A section header, `[name]`.
+    Header,
+    /// A key-value pair, including `root` and pairs that are ignored.
+    Pair,
+    /// Any other line, such as a `[` without a closing `]`.
+    Other,
+}
+
+impl EditorConfig {
+    /// The classified lines of the parsed source text, in order.
+    pub fn lines(&self) -> &[LineRecord] {
+        &self.lines
+    }
+
+    /// The byte range in the parsed source text of the section at `index`, as
+    /// [SectionSpans::source_range], e.g. to splice an edit into the original text.
+    /// For a section merged with [ParseOptions::merge_duplicate_sections], this is the range of
+    /// its first occurrence. `None` for sections that were not parsed from source text.
+    pub fn section_source_range(&self, index: usize) -> Option<Range<usize>> {
+        self.section_spans(index).map(|spans| spans.source_range())
+    }
+
+    /// The spans of the first occurrence of the section at `index`, like
+    /// [EditorConfig::parse_with_spans] records them, recovered from the line records.
+    fn section_spans(&self, index: usize) -> Option<SectionSpans> {
+        let header = self
+            .lines
+            .iter()
+            .position(|line| line.kind == LineKind::Header && line.section == Some(index))?;
+        let start = self.lines[header].span.start;
+        let name_len = self.sections[index].name.len();
+        let pairs = self.lines[header + 1..]
+            .iter()
+            .take_while(|line| line.kind != LineKind::Header && line.section == Some(index))
+            .filter_map(|line| Some(PairSpans { key: line.key?, value: line.value? }))
+            .collect();
+        Some(SectionSpans {
+            header: Span { start, end: start + name_len + 2 },
+            name: Span { start: start + 1, end: start + 1 + name_len },
+            pairs,
+        })
+    }
+
+    /// Parse like [EditorConfig::parse], also recording where each section header, key and
+    /// value is in `source_text`. The spans are in the same order as [EditorConfig::sections].
+    pub fn parse_with_spans(source_text: &str) -> (Self, Vec<SectionSpans>) {
+        let parsed = Self::parse_source(source_text, &ParseOptions::default(), true);
+        (parsed.editor_config, parsed.spans)
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, path::Path};
+
+use crate::{EditorConfig, EditorConfigProperties, EditorConfigProperty, IndentSize, IndentStyle};
+
+/// An EditorConfig version whose resolution behavior to reproduce, like the `-b` flag of the
+/// editorconfig-core CLI, see [ParseOptions::spec_version](crate::ParseOptions::spec_version).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SpecVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SpecVersion {
+    /// The first version where `indent_style = tab` implies `indent_size = tab`.
+    pub const V0_10_0: Self = Self::new(0, 10, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+}
+
+impl fmt::Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl EditorConfig {
+    /// Resolve like [EditorConfig::resolve], then apply the defaults of the specification:
+    /// `indent_size = tab` resolves to the `tab_width`, and `tab_width` defaults to a numeric
+    /// `indent_size`.
+    pub fn resolve_with_spec_defaults(&self, path: &Path) -> EditorConfigProperties {
+        let mut properties = self.resolve(path);
+        properties.apply_spec_defaults();
+        properties
+    }
+}
+
+impl EditorConfigProperties {
+    /// Fill in defaults after resolving, as editorconfig-core does for `version`: from 0.10.0,
+    /// `indent_style = tab` without an `indent_size` sets it to `tab`, then
+    /// [EditorConfigProperties::apply_spec_defaults].
+    pub(crate) fn apply_core_defaults(&mut self, version: SpecVersion) {
+        if version >= SpecVersion::V0_10_0
+            && self.indent_style == EditorConfigProperty::Value(IndentStyle::Tab)
+            && self.indent_size == EditorConfigProperty::None
+        {
+            self.indent_size = EditorConfigProperty::Value(IndentSize::Tab);
+        }
+        self.apply_spec_defaults();
+    }
+
+    /// `indent_size = tab` is the `tab_width` if there is one, and `tab_width` defaults to a
+    /// numeric `indent_size`.
+    fn apply_spec_defaults(&mut self) {
+        if self.indent_size == EditorConfigProperty::Value(IndentSize::Tab)
+            && let EditorConfigProperty::Value(tab_width) = self.tab_width
+        {
+            self.indent_size = EditorConfigProperty::Value(IndentSize::Number(tab_width));
+        }
+        if self.tab_width == EditorConfigProperty::None
+            && let EditorConfigProperty::Value(IndentSize::Number(indent_size)) = self.indent_size
+        {
+            self.tab_width = EditorConfigProperty::Value(indent_size);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::EditorConfigSection;
+
+/// How narrowly a section pattern targets files, ordered from least to most specific.
+///
+/// Sections apply in file order, so a broad section after a more specific one overrides it,
+/// which is usually a mistake.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Specificity {
+    /// Any other pattern with a wildcard, e.g. `*`, `**/test_*.rs` or `[Mm]akefile`.
+    Wildcard,
+    /// Files by extension, e.g. `*.rs`, `*.{js,ts}` or `docs/*.md`.
+    Extension,
+    /// Files by name or path without wildcards, e.g. `Makefile` or `src/{lib,main}.rs`.
+    Literal,
+}
+
+impl EditorConfigSection {
+    /// Classify this section's pattern by how narrowly it targets files.
+    pub fn specificity(&self) -> Specificity {
+        let (dir, name) = self.name.rsplit_once('/').unwrap_or(("", &self.name));
+        if !has_wildcard(&self.name) {
+            Specificity::Literal
+        } else if !has_wildcard(dir)
+            && let Some(extension) = name.strip_prefix("*.")
+            && !extension.is_empty()
+            && !has_wildcard(extension)
+        {
+            Specificity::Extension
+        } else {
+            Specificity::Wildcard
+        }
+    }
+}
+
+/// Which files a section pattern can match, see [EditorConfigSection::pattern_kind].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PatternKind {
+    /// Every file, e.g. `*`, `**` or `/**`.
+    MatchAll,
+    /// Files by extension in any directory, e.g. `*.rs` or `*.{js,ts}`.
+    ExtensionOnly,
+    /// Files by name without wildcards in any directory, e.g. `Makefile` or
+    /// `{package.json,.travis.yml}`.
+    FilenameOnly,
+    /// Files under particular directories, e.g. `src/**/*.rs` or `/docs/*.md`.
+    DirectoryAnchored,
+    /// Any other pattern without a `/`, e.g. `test_*.py` or `[Mm]akefile`.
+    Other,
+}
+
+impl EditorConfigSection {
+    /// Classify this section's pattern by which files it can match.
+    ///
+    /// Patterns without a `/` are classified by [EditorConfigSection::specificity].
+    pub fn pattern_kind(&self) -> PatternKind {
+        if matches!(self.name.as_str(), "*" | "**" | "**/*" | "**/**" | "/**" | "/**/*") {
+            return PatternKind::MatchAll;
+        }
+        if self.name.contains('/') {
+            return PatternKind::DirectoryAnchored;
+        }
+        match self.specificity() {
+            Specificity::Literal => PatternKind::FilenameOnly,
+            Specificity::Extension => PatternKind::ExtensionOnly,
+            Specificity::Wildcard => PatternKind::Other,
+        }
+    }
+
+    /// Whether this section only applies to files under particular directories,
+    /// see [PatternKind::DirectoryAnchored].
+    pub fn applies_only_to_directories(&self) -> bool {
+        self.pattern_kind() == PatternKind::DirectoryAnchored
+    }
+}
+
+/// Whether `pattern` contains an unescaped `*`, `?` or `[`.
+fn has_wildcard(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '*' | '?' | '[' => return true,
+            _ => {}
+        }
+    }
+    false
+}
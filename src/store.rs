@@ -0,0 +1,82 @@
+//! Configs shared between readers that resolve concurrently and an occasional writer.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::EditorConfig;
+
+/// The configs of a set of directories, replaced copy-on-write.
+///
+/// Readers take a [Snapshot], which only holds the lock long enough to clone an `Arc`, and
+/// resolve against it without locking. Writers build a new snapshot and swap it in, so readers
+/// never observe a partially updated store.
+#[derive(Debug, Default)]
+pub struct ConfigStore {
+    current: RwLock<Arc<Snapshot>>,
+}
+
+/// An immutable view of a [ConfigStore].
+#[derive(Debug, Default, Clone)]
+pub struct Snapshot {
+    version: u64,
+    configs: HashMap<PathBuf, Arc<EditorConfig>>,
+}
+
+impl ConfigStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current configs.
+    pub fn snapshot(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// The version of the current snapshot, incremented by every change.
+    pub fn version(&self) -> u64 {
+        self.snapshot().version
+    }
+
+    /// Set the config of `dir`, replacing any previous one. Returns the new version.
+    pub fn replace<P: Into<PathBuf>>(&self, dir: P, config: EditorConfig) -> u64 {
+        let dir = dir.into();
+        self.update(|configs| {
+            configs.insert(dir, Arc::new(config));
+        })
+    }
+
+    /// Remove the config of `dir`. Returns the new version.
+    pub fn remove(&self, dir: &Path) -> u64 {
+        self.update(|configs| {
+            configs.remove(dir);
+        })
+    }
+
+    fn update<F: FnOnce(&mut HashMap<PathBuf, Arc<EditorConfig>>)>(&self, f: F) -> u64 {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let mut configs = current.configs.clone();
+        f(&mut configs);
+        let version = current.version + 1;
+        *current = Arc::new(Snapshot { version, configs });
+        version
+    }
+}
+
+impl Snapshot {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The config of `dir`.
+    pub fn get(&self, dir: &Path) -> Option<&Arc<EditorConfig>> {
+        self.configs.get(dir)
+    }
+
+    /// The directories with a config, in no particular order.
+    pub fn dirs(&self) -> impl Iterator<Item = &Path> {
+        self.configs.keys().map(PathBuf::as_path)
+    }
+}
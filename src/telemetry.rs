@@ -0,0 +1,44 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::EditorConfig;
+
+/// Receives an event for every path resolved by an [EditorConfig], e.g. to feed an editor's
+/// telemetry. See [EditorConfig::with_resolve_observer].
+pub trait ResolveObserver: Send + Sync {
+    fn resolved(&self, event: &ResolveEvent<'_>);
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ResolveEvent<'a> {
+    /// The path matched against section globs, relative to the config file's directory,
+    /// as bytes separated by `/`.
+    pub path: &'a [u8],
+
+    /// The time spent matching and merging.
+    pub duration: Duration,
+
+    /// The number of sections whose globs were matched against the path.
+    pub candidates: usize,
+
+    pub matched_sections: usize,
+
+    /// Whether the merged properties of the matching sections were already cached.
+    pub cache_hit: bool,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Observer(pub(crate) Option<Arc<dyn ResolveObserver>>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Observer(Some(..))" } else { "Observer(None)" })
+    }
+}
+
+impl EditorConfig {
+    /// Call `observer` after resolving each path, with timing and cache data.
+    pub fn with_resolve_observer(mut self, observer: Arc<dyn ResolveObserver>) -> Self {
+        self.observer = Observer(Some(observer));
+        self
+    }
+}
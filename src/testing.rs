@@ -0,0 +1,87 @@
+//! Helpers for regression tests against a corpus of `.editorconfig` files.
+//!
+//! ```ignore
+//! editorconfig_parser::assert_resolves!("[*.rs]\nindent_size = 4", "src/main.rs", "indent_size = 4");
+//! ```
+
+use std::{fs, io, path::Path, sync::atomic::AtomicBool};
+
+use crate::{EditorConfig, EditorConfigSection, cancel};
+
+/// Check that `path` resolves to exactly the properties in `expected`, written as the body of
+/// a section, e.g. `"indent_style = space\nindent_size = 2"`.
+///
+/// # Errors
+///
+/// Returns a message describing the mismatch.
+pub fn check_resolves(config: &str, path: &str, expected: &str) -> Result<(), String> {
+    let expected = EditorConfigSection::parse_body(path, expected).properties;
+    let resolved = EditorConfig::parse(config).resolve(Path::new(path));
+    if resolved == expected {
+        Ok(())
+    } else {
+        Err(format!("`{path}` resolved to {resolved:?}, expected {expected:?}"))
+    }
+}
+
+/// Assert that a path resolves to exactly the expected properties, see [check_resolves].
+#[macro_export]
+macro_rules! assert_resolves {
+    ($config:expr, $path:expr, $expected:expr $(,)?) => {
+        if let Err(message) = $crate::testing::check_resolves($config, $path, $expected) {
+            panic!("{message}");
+        }
+    };
+}
+
+/// Run every fixture in `dir`, returning a message for each failed expectation.
+///
+/// A fixture is a subdirectory holding an `.editorconfig` and an `expected.ini`, whose sections
+/// name paths and list the properties each one resolves to:
+///
+/// ```ini
+/// [src/main.rs]
+/// indent_size = 4
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a fixture cannot be read.
+pub fn run_fixtures<P: AsRef<Path>>(dir: P) -> io::Result<Vec<String>> {
+    run_fixtures_with_cancel(dir, &AtomicBool::new(false))
+}
+
+/// Run fixtures like [run_fixtures], checking `cancel` before each fixture.
+///
+/// # Errors
+///
+/// Returns an error if a fixture cannot be read, or an [io::ErrorKind::Interrupted] error
+/// wrapping [Cancelled](crate::Cancelled) if `cancel` is set.
+pub fn run_fixtures_with_cancel<P: AsRef<Path>>(
+    dir: P,
+    cancel: &AtomicBool,
+) -> io::Result<Vec<String>> {
+    let mut fixtures = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    fixtures.retain(|path| path.is_dir());
+    fixtures.sort();
+    let mut failures = vec![];
+    for fixture in fixtures {
+        cancel::check(cancel)?;
+        let config = EditorConfig::parse(&fs::read_to_string(fixture.join(".editorconfig"))?);
+        let expected = EditorConfig::parse(&fs::read_to_string(fixture.join("expected.ini"))?);
+        for section in expected.sections() {
+            let resolved = config.resolve(Path::new(&section.name));
+            if resolved != section.properties {
+                failures.push(format!(
+                    "{}: `{}` resolved to {resolved:?}, expected {:?}",
+                    fixture.display(),
+                    section.name,
+                    section.properties
+                ));
+            }
+        }
+    }
+    Ok(failures)
+}
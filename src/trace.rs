@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use crate::{
+    EditorConfig, EditorConfigProperties, EditorConfigProperty, LineKind, PropertyKey,
+    glob::Candidate,
+};
+
+/// The section that supplied a resolved property, see [EditorConfig::resolve_traced].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PropertySource {
+    /// The index in [EditorConfig::sections].
+    pub section: usize,
+
+    /// The section name.
+    pub pattern: String,
+
+    /// The 1-based line of the section header, `None` for sections that were not parsed from
+    /// source text.
+    pub line: Option<usize>,
+}
+
+/// Properties resolved for a path with where each came from, see [EditorConfig::resolve_traced].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Traced {
+    /// The same as [EditorConfig::resolve].
+    pub properties: EditorConfigProperties,
+
+    /// The source of each property set in `properties`, in the order of [PropertyKey::ALL].
+    /// Properties only set by defaults, e.g. [ParseOptions::spec_version](crate::ParseOptions::spec_version),
+    /// have none.
+    pub sources: Vec<(PropertyKey, PropertySource)>,
+}
+
+impl Traced {
+    /// The source of `key`, if it is set by a section.
+    pub fn source(&self, key: PropertyKey) -> Option<&PropertySource> {
+        self.sources.iter().find(|(k, _)| *k == key).map(|(_, source)| source)
+    }
+}
+
+impl EditorConfig {
+    /// Resolve like [EditorConfig::resolve], also finding the section that supplied the value
+    /// of each property, e.g. to explain why a file is formatted the way it is.
+    pub fn resolve_traced(&self, path: &Path) -> Traced {
+        let properties = self.resolve(path);
+        let Some(path) = self.relative_path(path) else {
+            return Traced { properties, sources: vec![] };
+        };
+        let candidate = Candidate::from_bytes(&path);
+        // The last matching section setting each property, cleared by `unset`.
+        let mut winners: [Option<usize>; PropertyKey::ALL.len()] = Default::default();
+        for (i, section) in self.sections.iter().enumerate() {
+            if !section.matcher.as_ref().is_some_and(|m| m.is_match_candidate(&candidate)) {
+                continue;
+            }
+            for key in PropertyKey::ALL {
+                match section.properties.get(key) {
+                    EditorConfigProperty::Value(_) => winners[key as usize] = Some(i),
+                    EditorConfigProperty::Unset => winners[key as usize] = None,
+                    EditorConfigProperty::None => {}
+                }
+            }
+        }
+        let sources = properties
+            .iter()
+            .filter_map(|(key, _)| {
+                let section = winners[key as usize]?;
+                Some((key, self.property_source(section)))
+            })
+            .collect();
+        Traced { properties, sources }
+    }
+
+    fn property_source(&self, section: usize) -> PropertySource {
+        let line = self
+            .lines
+            .iter()
+            .position(|line| line.kind == LineKind::Header && line.section == Some(section))
+            .map(|i| i + 1);
+        PropertySource { section, pattern: self.sections[section].name.clone(), line }
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::{
+    Complexity, EditorConfig, EditorConfigDocument, GitignoreIsm, ParseError, complexity,
+    gitignore_isms,
+};
+
+/// Edits to an [EditorConfigDocument] applied together, only if the result is valid.
+#[derive(Debug, Clone)]
+pub struct DocumentTransaction<'a> {
+    document: &'a EditorConfigDocument,
+    edits: Vec<Edit>,
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Set { section: String, key: String, value: String },
+    Remove { section: String, key: String },
+}
+
+/// A problem with the result of a [DocumentTransaction].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EditViolation {
+    /// A section name, key or value contains a line break, which would add lines.
+    LineBreak { section: String, key: String },
+    /// An edited line of the result fails [EditorConfig::try_parse].
+    Parse(ParseError),
+    /// A section pattern uses a gitignore idiom, see [gitignore_isms].
+    GitignoreIsm { section: String, ism: GitignoreIsm },
+    /// A section pattern is likely to slow down resolving, see [Complexity::is_slow].
+    SlowGlob { section: String, complexity: Complexity },
+}
+
+impl fmt::Display for EditViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LineBreak { section, key } => {
+                write!(f, "`{key}` in section `[{section}]` contains a line break")
+            }
+            Self::Parse(err) => err.fmt(f),
+            Self::GitignoreIsm { section, ism } => {
+                write!(f, "section `[{section}]` uses a gitignore idiom ({:?})", ism.kind)
+            }
+            Self::SlowGlob { section, complexity } => write!(
+                f,
+                "section `[{section}]` expands to {} patterns with {} wildcards",
+                complexity.expansions, complexity.wildcards
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditViolation {}
+
+impl EditorConfigDocument {
+    /// Start queuing edits to apply together, see [DocumentTransaction::commit].
+    pub fn transaction(&self) -> DocumentTransaction<'_> {
+        DocumentTransaction { document: self, edits: vec![] }
+    }
+}
+
+impl DocumentTransaction<'_> {
+    /// Queue [EditorConfigDocument::set].
+    #[must_use]
+    pub fn set(mut self, section: &str, key: &str, value: &str) -> Self {
+        let (section, key, value) = (section.to_string(), key.to_string(), value.to_string());
+        self.edits.push(Edit::Set { section, key, value });
+        self
+    }
+
+    /// Queue [EditorConfigDocument::remove].
+    #[must_use]
+    pub fn remove(mut self, section: &str, key: &str) -> Self {
+        self.edits.push(Edit::Remove { section: section.to_string(), key: key.to_string() });
+        self
+    }
+
+    /// Apply the edits to a copy of the document, returning it if the edited sections and pairs
+    /// parse strictly, and the edited sections have no patterns with gitignore idioms or slow
+    /// globs. Problems elsewhere in the document are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns every violation of the edits, leaving the document unchanged.
+    pub fn commit(self) -> Result<EditorConfigDocument, Vec<EditViolation>> {
+        let mut document = self.document.clone();
+        let mut violations = vec![];
+        let mut edited = vec![];
+        for edit in self.edits {
+            match edit {
+                Edit::Set { section, key, value } => {
+                    if [&section, &key, &value].iter().any(|text| text.contains(['\n', '\r'])) {
+                        violations.push(EditViolation::LineBreak { section, key });
+                        continue;
+                    }
+                    document.set(&section, &key, &value);
+                    edited.push((section, key));
+                }
+                Edit::Remove { section, key } => {
+                    document.remove(&section, &key);
+                }
+            }
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+        // 1-based lines of the edited headers and pairs.
+        let mut lines = vec![];
+        for (section, key) in &edited {
+            lines.extend(document.find_section(section).map(|range| range.start));
+            lines.extend(document.find_pair(section, key).map(|i| i + 1));
+        }
+        if let Err(errors) = EditorConfig::try_parse(&document.to_string()) {
+            violations.extend(
+                errors
+                    .into_iter()
+                    .filter(|err| lines.contains(&err.line))
+                    .map(EditViolation::Parse),
+            );
+            if !violations.is_empty() {
+                return Err(violations);
+            }
+        }
+        let mut sections: Vec<&String> = edited.iter().map(|(section, _)| section).collect();
+        sections.sort_unstable();
+        sections.dedup();
+        for section in sections {
+            for ism in gitignore_isms(section) {
+                violations.push(EditViolation::GitignoreIsm { section: section.clone(), ism });
+            }
+            let complexity = complexity(section);
+            if complexity.is_slow() {
+                let section = section.clone();
+                violations.push(EditViolation::SlowGlob { section, complexity });
+            }
+        }
+        if violations.is_empty() { Ok(document) } else { Err(violations) }
+    }
+}
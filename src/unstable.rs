@@ -0,0 +1,119 @@
+//! Stand-ins for the APIs behind the `unstable` feature, which fail to compile when used,
+//! pointing at the feature instead of reporting that the API does not exist.
+//!
+//! Parameters match the real APIs; results are `!`, as their types are behind the feature too.
+
+use std::{path::Path, sync::atomic::AtomicBool};
+
+use crate::EditorConfig;
+
+mod private {
+    pub trait Sealed {}
+
+    /// Names the missing feature in the bound that fails.
+    pub struct Unstable;
+}
+
+/// Not implemented for any type, so that calling an API behind the `unstable` feature fails to
+/// compile without it.
+#[diagnostic::on_unimplemented(
+    message = "this API requires the `unstable` feature of `editorconfig-parser`",
+    note = "enable it with `editorconfig-parser = {{ version = \"*\", features = [\"unstable\"] }}`"
+)]
+pub trait RequiresUnstableFeature: private::Sealed {}
+
+const UNREACHABLE: &str = "`RequiresUnstableFeature` is not implemented for any type";
+
+pub mod corpus {
+    use super::{AtomicBool, Path, RequiresUnstableFeature, UNREACHABLE, private::Unstable};
+
+    /// Requires the `unstable` feature.
+    pub fn ingest<P: AsRef<Path>>(_dir: P) -> !
+    where
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+
+    /// Requires the `unstable` feature.
+    pub fn ingest_with_cancel<P: AsRef<Path>>(_dir: P, _cancel: &AtomicBool) -> !
+    where
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+}
+
+pub mod matrix {
+    use super::{EditorConfig, RequiresUnstableFeature, UNREACHABLE, private::Unstable};
+
+    /// Requires the `unstable` feature.
+    pub fn settings_matrix(_configs: &[EditorConfig], _extensions: &[&str]) -> !
+    where
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+}
+
+pub mod reduce {
+    use super::{RequiresUnstableFeature, UNREACHABLE, private::Unstable};
+
+    /// Requires the `unstable` feature.
+    pub fn reduce<F>(_source_text: &str, _reproduces: F) -> !
+    where
+        F: FnMut(&str) -> bool,
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+}
+
+pub mod store {
+    use super::{RequiresUnstableFeature, UNREACHABLE, private::Unstable};
+
+    /// Requires the `unstable` feature.
+    pub struct ConfigStore(());
+
+    impl ConfigStore {
+        /// Requires the `unstable` feature.
+        pub fn new() -> Self
+        where
+            for<'a> &'a Unstable: RequiresUnstableFeature,
+        {
+            unreachable!("{UNREACHABLE}")
+        }
+    }
+
+    impl Default for ConfigStore
+    where
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        /// Requires the `unstable` feature.
+        fn default() -> Self {
+            unreachable!("{UNREACHABLE}")
+        }
+    }
+}
+
+pub mod workspace {
+    use super::{EditorConfig, Path, RequiresUnstableFeature, UNREACHABLE, private::Unstable};
+
+    /// Requires the `unstable` feature.
+    pub fn write_configs<P: AsRef<Path>, O>(_configs: &[(P, EditorConfig)], _options: &O) -> !
+    where
+        for<'a> &'a Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+}
+
+impl EditorConfig {
+    /// Requires the `unstable` feature.
+    pub fn for_directory(&self, _dir: &Path) -> !
+    where
+        for<'a> &'a private::Unstable: RequiresUnstableFeature,
+    {
+        unreachable!("{UNREACHABLE}")
+    }
+}
@@ -0,0 +1,194 @@
+//! Parsing of property values, with the same semantics as [EditorConfig::parse](crate::EditorConfig::parse).
+//!
+//! Values are case-insensitive, and `unset` parses to [EditorConfigProperty::Unset] for every property.
+//! Invalid values parse to [EditorConfigProperty::None], except that the enum properties keep
+//! a single word outside the specification as `Other`, e.g. [EndOfLine::Other] for `native`.
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use crate::{
+    Charset, EditorConfigProperties, EditorConfigProperty, EndOfLine, IndentSize, IndentStyle,
+    MaxLineLength,
+};
+
+/// Normalize a value like editorconfig-core: values of known properties are lowercased,
+/// values of other keys keep their case. `key` is case-insensitive.
+pub fn normalize<'a>(key: &str, value: &'a str) -> Cow<'a, str> {
+    let known = EditorConfigProperties::KEYS.iter().any(|known| known.eq_ignore_ascii_case(key));
+    if known && value.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(value.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+fn is_unset(s: &str) -> bool {
+    s.eq_ignore_ascii_case("unset")
+}
+
+/// A value outside the specification, lowercased, if it is a single word of ASCII letters,
+/// digits, `-` and `_`, such as `utf-16` or `native`.
+fn other<T>(s: &str, f: fn(String) -> T) -> EditorConfigProperty<T> {
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_') {
+        EditorConfigProperty::Value(f(s.to_ascii_lowercase()))
+    } else {
+        EditorConfigProperty::None
+    }
+}
+
+/// `tab` or `space`.
+pub fn parse_indent_style(s: &str) -> EditorConfigProperty<IndentStyle> {
+    if s.eq_ignore_ascii_case("tab") {
+        EditorConfigProperty::Value(IndentStyle::Tab)
+    } else if s.eq_ignore_ascii_case("space") {
+        EditorConfigProperty::Value(IndentStyle::Space)
+    } else if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else {
+        other(s, IndentStyle::Other)
+    }
+}
+
+/// A whole number of columns, or `tab`.
+pub fn parse_indent_size(s: &str) -> EditorConfigProperty<IndentSize> {
+    if s.eq_ignore_ascii_case("tab") {
+        EditorConfigProperty::Value(IndentSize::Tab)
+    } else {
+        parse_tab_width(s).map(IndentSize::Number)
+    }
+}
+
+/// A whole number of columns.
+pub fn parse_tab_width(s: &str) -> EditorConfigProperty<usize> {
+    if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else {
+        s.parse::<usize>().map_or(EditorConfigProperty::None, EditorConfigProperty::Value)
+    }
+}
+
+/// `lf`, `cr` or `crlf`.
+pub fn parse_end_of_line(s: &str) -> EditorConfigProperty<EndOfLine> {
+    if s.eq_ignore_ascii_case("lf") {
+        EditorConfigProperty::Value(EndOfLine::Lf)
+    } else if s.eq_ignore_ascii_case("cr") {
+        EditorConfigProperty::Value(EndOfLine::Cr)
+    } else if s.eq_ignore_ascii_case("crlf") {
+        EditorConfigProperty::Value(EndOfLine::Crlf)
+    } else if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else {
+        other(s, EndOfLine::Other)
+    }
+}
+
+/// `latin1`, `utf-8`, `utf-8-bom`, `utf-16be` or `utf-16le`.
+pub fn parse_charset(s: &str) -> EditorConfigProperty<Charset> {
+    if s.eq_ignore_ascii_case("utf-8") {
+        EditorConfigProperty::Value(Charset::Utf8)
+    } else if s.eq_ignore_ascii_case("latin1") {
+        EditorConfigProperty::Value(Charset::Latin1)
+    } else if s.eq_ignore_ascii_case("utf-16be") {
+        EditorConfigProperty::Value(Charset::Utf16be)
+    } else if s.eq_ignore_ascii_case("utf-16le") {
+        EditorConfigProperty::Value(Charset::Utf16le)
+    } else if s.eq_ignore_ascii_case("utf-8-bom") {
+        EditorConfigProperty::Value(Charset::Utf8bom)
+    } else if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else {
+        other(s, Charset::Other)
+    }
+}
+
+/// `true` or `false`, as for `trim_trailing_whitespace` and `insert_final_newline`.
+pub fn parse_bool(s: &str) -> EditorConfigProperty<bool> {
+    if s.eq_ignore_ascii_case("true") {
+        EditorConfigProperty::Value(true)
+    } else if s.eq_ignore_ascii_case("false") {
+        EditorConfigProperty::Value(false)
+    } else if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else {
+        EditorConfigProperty::None
+    }
+}
+
+/// A whole number of columns, or `off`.
+pub fn parse_max_line_length(s: &str) -> EditorConfigProperty<MaxLineLength> {
+    if s.eq_ignore_ascii_case("off") {
+        EditorConfigProperty::Value(MaxLineLength::Off)
+    } else if is_unset(s) {
+        EditorConfigProperty::Unset
+    } else if let Ok(n) = s.parse::<usize>() {
+        EditorConfigProperty::Value(MaxLineLength::Number(n))
+    } else {
+        EditorConfigProperty::None
+    }
+}
+
+/// A value that [FromStr] could not parse, including `unset`, which is not a value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseValueError {
+    pub value: String,
+}
+
+impl fmt::Display for ParseValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value `{}`", self.value)
+    }
+}
+
+impl std::error::Error for ParseValueError {}
+
+fn from_property<T>(s: &str, property: EditorConfigProperty<T>) -> Result<T, ParseValueError> {
+    match property {
+        EditorConfigProperty::Value(value) => Ok(value),
+        EditorConfigProperty::None | EditorConfigProperty::Unset => {
+            Err(ParseValueError { value: s.to_string() })
+        }
+    }
+}
+
+/// Like [parse_indent_style], so any other nonempty value is [IndentStyle::Other].
+impl FromStr for IndentStyle {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_property(s, parse_indent_style(s))
+    }
+}
+
+/// Like [parse_end_of_line], so any other nonempty value is [EndOfLine::Other].
+impl FromStr for EndOfLine {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_property(s, parse_end_of_line(s))
+    }
+}
+
+/// Like [parse_charset], so any other nonempty value is [Charset::Other].
+impl FromStr for Charset {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_property(s, parse_charset(s))
+    }
+}
+
+impl FromStr for IndentSize {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_property(s, parse_indent_size(s))
+    }
+}
+
+impl FromStr for MaxLineLength {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_property(s, parse_max_line_length(s))
+    }
+}
@@ -0,0 +1,119 @@
+//! Writing the `.editorconfig` files of a whole workspace at once.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::EditorConfig;
+
+/// Options for [write_configs].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct WriteOptions {
+    /// Only report what would be written, without touching the file system.
+    pub dry_run: bool,
+}
+
+/// A `.editorconfig` file written, or to be written with [WriteOptions::dry_run].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConfigWrite {
+    pub path: PathBuf,
+
+    /// Whether the serialized config differs from the file's current contents.
+    /// Unchanged files are not written.
+    pub changed: bool,
+}
+
+/// Serialize each config into the `.editorconfig` of its directory, all or nothing.
+///
+/// Every file is first written to a temporary file next to it, then renamed into place.
+/// If any step fails, temporary files are removed and files already replaced are restored.
+///
+/// # Errors
+///
+/// Returns an [io::ErrorKind::InvalidInput] error if a directory is listed twice, before
+/// touching any file, or else the first error reading, writing or renaming a file.
+pub fn write_configs<P: AsRef<Path>>(
+    configs: &[(P, EditorConfig)],
+    options: &WriteOptions,
+) -> io::Result<Vec<ConfigWrite>> {
+    let mut planned = vec![];
+    let mut paths = HashSet::new();
+    for (dir, config) in configs {
+        let path = dir.as_ref().join(".editorconfig");
+        if !paths.insert(path.clone()) {
+            let message = format!("`{}` is listed more than once", dir.as_ref().display());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        // Compared as bytes, as the file need not be UTF-8.
+        let previous = match fs::read(&path) {
+            Ok(previous) => Some(previous),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+        let contents = config.to_string();
+        planned.push((path, previous, contents));
+    }
+    let writes = planned
+        .iter()
+        .map(|(path, previous, contents)| ConfigWrite {
+            path: path.clone(),
+            changed: previous.as_deref() != Some(contents.as_bytes()),
+        })
+        .collect::<Vec<_>>();
+    if options.dry_run {
+        return Ok(writes);
+    }
+    let changed = planned
+        .into_iter()
+        .filter(|(_, previous, contents)| previous.as_deref() != Some(contents.as_bytes()))
+        .collect::<Vec<_>>();
+
+    let mut staged = vec![];
+    for (path, _, contents) in &changed {
+        let temp = temp_path(path);
+        if let Err(err) = fs::write(&temp, contents) {
+            remove_all(&staged);
+            return Err(err);
+        }
+        staged.push(temp);
+    }
+
+    for (i, ((path, _, _), temp)) in changed.iter().zip(&staged).enumerate() {
+        if let Err(err) = fs::rename(temp, path) {
+            for (path, previous, _) in &changed[..i] {
+                // Best effort: the original error is more useful than a failed rollback.
+                let _ = restore(path, previous.as_deref());
+            }
+            remove_all(&staged[i..]);
+            return Err(err);
+        }
+    }
+    Ok(writes)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".tmp-{}", process::id()));
+    path.with_file_name(name)
+}
+
+fn remove_all(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Put back the previous contents of `path`, or remove it if it did not exist.
+fn restore(path: &Path, previous: Option<&[u8]>) -> io::Result<()> {
+    match previous {
+        Some(previous) => {
+            let temp = temp_path(path);
+            fs::write(&temp, previous)?;
+            fs::rename(temp, path)
+        }
+        None => fs::remove_file(path),
+    }
+}
@@ -0,0 +1,194 @@
+use editorconfig_parser::{
+    Complexity, EditorConfigGlob, GitignoreIsm, GitignoreIsmKind, complexity, gitignore_isms,
+};
+
+fn is_match(pattern: &str, path: &str) -> bool {
+    EditorConfigGlob::new(pattern).unwrap().is_match(path)
+}
+
+#[test]
+fn new() {
+    let glob = EditorConfigGlob::new("*.{js,ts}").unwrap();
+    assert_eq!(glob.pattern(), "*.{js,ts}");
+    assert!(glob.is_match("src/index.ts"));
+
+    assert!(glob.is_match_bytes(b"src/\xff.ts"));
+
+    let err = EditorConfigGlob::new("[z-a]").unwrap_err();
+    assert_eq!(err.pattern, "[z-a]");
+}
+
+#[test]
+fn star() {
+    assert!(is_match("*.rs", "main.rs"));
+    assert!(is_match("*.rs", "src/main.rs"));
+    assert!(is_match("src/*.rs", "src/main.rs"));
+    assert!(!is_match("src/*.rs", "src/bin/main.rs"));
+}
+
+#[test]
+fn dotfiles() {
+    assert!(is_match("*", ".gitignore"));
+    assert!(is_match("*", "src/.gitignore"));
+    assert!(is_match("*.yml", ".eslintrc.yml"));
+    assert!(is_match("*.yml", "packages/app/.eslintrc.yml"));
+    assert!(is_match("**.yml", ".github/workflows/ci.yml"));
+    assert!(is_match("?eslintrc.yml", ".eslintrc.yml"));
+}
+
+#[test]
+fn double_star() {
+    assert!(is_match("**", "a/b/c"));
+    assert!(is_match("src/**/*.rs", "src/main.rs"));
+    assert!(is_match("src/**/*.rs", "src/a/b/main.rs"));
+
+    // Embedded in a path component.
+    for path in ["az", "abz", "a/z", "a/b/z", "ab/cz"] {
+        assert!(is_match("a**z", path), "{path}");
+    }
+    assert!(!is_match("a**z", "a/b"));
+
+    for path in ["lib/a.js", "lib/a/b.js", "lib/a/b/c.js"] {
+        assert!(is_match("lib/**.js", path), "{path}");
+    }
+    assert!(!is_match("lib/**.js", "src/a.js"));
+
+    for path in ["a.js", "x/a.js", "x/y/a.js"] {
+        assert!(is_match("**.js", path), "{path}");
+    }
+
+    assert!(is_match("{a**b,c}", "x/a/y/b"));
+    assert!(is_match("{a**b,c}", "c"));
+}
+
+#[cfg(not(feature = "spec-strict"))]
+#[test]
+fn trailing_slash() {
+    // `dir/` matches every file under the directory next to the `.editorconfig`.
+    assert!(is_match("src/", "src/main.rs"));
+    assert!(is_match("src/", "src/bin/main.rs"));
+    assert!(is_match("src/", "src/"));
+    assert!(!is_match("src/", "src"));
+    assert!(!is_match("src/", "crates/src/main.rs"));
+    assert!(is_match("vendor/**/", "vendor/a/b.js"));
+    assert!(is_match("/build/", "build/out.txt"));
+}
+
+#[cfg(feature = "spec-strict")]
+#[test]
+fn spec_strict_trailing_slash() {
+    // Only the path `src/` itself, as written in the specification.
+    assert!(is_match("src/", "src/"));
+    assert!(!is_match("src/", "src/main.rs"));
+}
+
+#[test]
+fn braces() {
+    for path in ["a.txt", "b.txt", "c.txt"] {
+        assert!(is_match("{a,{b,c}}.txt", path), "{path}");
+    }
+    assert!(!is_match("{a,{b,c}}.txt", "d.txt"));
+    assert!(is_match("{single}.txt", "{single}.txt"));
+    assert!(!is_match("{single}.txt", "single.txt"));
+    assert!(is_match("{a,b.txt", "{a,b.txt"));
+}
+
+#[test]
+fn fast_path() {
+    // `[*]` and `[*.ext]` skip the glob engine, and must agree with it.
+    let paths = ["", "a", "a.rs", ".rs", "a/b.rs", "a.rs/b", "a/", "a.d.rs", "a.RS", "a.rsx"];
+    for (fast, slow) in [("*", "**/*"), ("*.rs", "**/*.rs"), ("*.d.rs", "**/*.d.rs")] {
+        for path in paths {
+            assert_eq!(is_match(fast, path), is_match(slow, path), "{fast} {path}");
+        }
+    }
+}
+
+#[test]
+fn numeric_range() {
+    for path in ["file1.txt", "file2.txt", "file3.txt"] {
+        assert!(is_match("file{1..3}.txt", path), "{path}");
+    }
+    assert!(!is_match("file{1..3}.txt", "file4.txt"));
+    assert!(!is_match("file{1..3}.txt", "file{1..3}.txt"));
+    assert!(is_match("{-1..1}", "-1"));
+    assert!(is_match("{3..1}", "2"));
+    assert!(is_match("{1..}", "{1..}"));
+
+    // The same with both backends, following editorconfig-core: any integer in the range.
+    for (pattern, path, matches) in [
+        ("{1..3}.txt", "2.txt", true),
+        ("{1..3}.txt", "02.txt", true),
+        ("{1..3}.txt", "+2.txt", true),
+        ("{1..3}.txt", "4.txt", false),
+        ("{1..3}.txt", "-2.txt", false),
+        ("{1..3}.txt", "2a.txt", false),
+        ("{-3..-1}", "-02", true),
+        ("x{a,{1..3}}", "x03", true),
+        ("x{a,{1..3}}", "xa", true),
+        ("src/{1..3}/*.rs", "src/2/main.rs", true),
+        ("src/{1..3}/*.rs", "src/2/bin/main.rs", false),
+    ] {
+        assert_eq!(is_match(pattern, path), matches, "{pattern} {path}");
+    }
+}
+
+#[test]
+fn class() {
+    assert!(is_match("[ab].txt", "a.txt"));
+    assert!(!is_match("[ab].txt", "c.txt"));
+    assert!(is_match("[!ab].txt", "c.txt"));
+    assert!(!is_match("[!ab].txt", "a.txt"));
+    assert!(is_match("[a-c].txt", "b.txt"));
+    assert!(is_match("[a/b].txt", "[a/b].txt"));
+
+    // `!` and `^` are literal unless they start the class, with both backends.
+    for (pattern, matching) in [
+        ("[\\!\\^]", &["!", "^"][..]),
+        ("[\\^!-#]", &["!", "\"", "#", "^"]),
+        ("[\\^\\-]", &["^", "-"]),
+        ("[\\!]", &["!"]),
+    ] {
+        for path in matching {
+            assert!(is_match(pattern, path), "{pattern} {path}");
+        }
+        assert!(!is_match(pattern, "x"), "{pattern}");
+    }
+}
+
+#[test]
+fn gitignore() {
+    assert!(gitignore_isms("*.rs").is_empty());
+    assert!(gitignore_isms("\\!important").is_empty());
+    assert_eq!(
+        gitignore_isms("!*.rs"),
+        [GitignoreIsm { kind: GitignoreIsmKind::Negation, suggestion: None }]
+    );
+    assert_eq!(
+        gitignore_isms("build/"),
+        [GitignoreIsm {
+            kind: GitignoreIsmKind::TrailingSlash,
+            suggestion: Some("**/build/**".to_string())
+        }]
+    );
+    assert_eq!(
+        gitignore_isms("!src/generated/"),
+        [GitignoreIsm { kind: GitignoreIsmKind::Negation, suggestion: None }]
+    );
+}
+
+#[test]
+fn glob_complexity() {
+    assert_eq!(complexity("*.rs"), Complexity { expansions: 1, wildcards: 2 });
+    assert_eq!(complexity("/src/{a,b}{c,d}.rs"), Complexity { expansions: 4, wildcards: 0 });
+    assert_eq!(complexity("/file{1..10}"), Complexity { expansions: 10, wildcards: 0 });
+    assert!(!complexity("{src,lib}/**/*.{js,ts}").is_slow());
+
+    let slow = "{a,b,c,d}/**/{e,f,g,h}/**/{i,j,k,l}/**/*.{m,n,o,p}";
+    assert_eq!(complexity(slow), Complexity { expansions: 256, wildcards: 4 });
+    assert!(complexity(slow).is_slow());
+    let config = editorconfig_parser::EditorConfig::parse(&format!("[*]\n[{slow}]"));
+    let slow_sections = config.slow_sections();
+    assert_eq!(slow_sections.len(), 1);
+    assert_eq!(slow_sections[0].0.name, slow);
+}
@@ -1,8 +1,20 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use editorconfig_parser::{
-    Charset, EditorConfig, EditorConfigProperties, EditorConfigProperty::Value, EndOfLine,
-    IndentStyle, MaxLineLength,
+    BuildError, Cancelled, Charset, Diagnostic, DiagnosticKind, EditViolation, EditorConfig,
+    EditorConfigDocument, EditorConfigGlob, EditorConfigProperties,
+    EditorConfigProperty::{self, Value},
+    EditorConfigSection, EndOfLine, GlobErrorKind, GlobLimits, IndentSize, IndentStyle,
+    InputLimits, LineKind, LineRecord, MatchOptions, MaxLineLength, OutsideCwd, PairSpans,
+    ParseError, ParseErrorKind, ParseOptions, PatternKind, PropertyKey, PropertyRegistry,
+    PropertySource, PropertyValue, ResolveError, ResolveEvent, ResolveObserver, ResolveOptions,
+    Resolved, RoundTripDivergence, RoundTripDivergenceKind, SectionSpans, Span, SpecVersion,
+    Specificity, UnknownPropertyKey, ValueSyntax, completions, detect,
+    diagnostic::{self, Message},
+    semantics_version, value,
 };
 
 #[test]
@@ -28,6 +40,149 @@ fn root() {
 
     let editor_config = EditorConfig::parse("root = false");
     assert!(!editor_config.root());
+    assert_eq!(editor_config.root_value(), Some(false));
+    assert_eq!(editor_config.to_string(), "root = false\n");
+
+    assert_eq!(EditorConfig::parse("ROOT = True").root_value(), Some(true));
+    assert_eq!(EditorConfig::parse("root = yes").root_value(), Some(false));
+    assert_eq!(EditorConfig::parse("[*]\nroot = true").root_value(), None);
+}
+
+#[test]
+fn parse_with_diagnostics() {
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [[z-a].txt]
+        indent_size = 2
+    ",
+    );
+    assert_eq!(editor_config.sections().len(), 2);
+    assert!(editor_config.sections()[1].matcher.is_none());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 5);
+    assert!(matches!(
+        &diagnostics[0].kind,
+        DiagnosticKind::InvalidGlob { pattern, .. } if pattern == "[z-a].txt"
+    ));
+    assert!(diagnostics[0].to_string().starts_with("line 5: invalid glob `[z-a].txt`"));
+}
+
+#[test]
+fn glob_limits() {
+    let source = format!("[{}]\nindent_size = 2\n[{}]\n[a]", "{a,b}".repeat(11), "a**".repeat(64));
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(&source);
+    assert!(editor_config.sections()[0].matcher.is_none());
+    assert!(editor_config.sections()[1].matcher.is_none());
+    assert!(editor_config.sections()[2].matcher.is_some());
+    assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.line).collect::<Vec<_>>(), [1, 3]);
+    assert!(diagnostics[0].to_string().ends_with("pattern expands to more than 1024 alternatives"));
+
+    let options = ParseOptions {
+        glob_limits: GlobLimits { max_pattern_len: 12, max_expansions: 4 },
+        ..ParseOptions::default()
+    };
+    let (editor_config, diagnostics) =
+        EditorConfig::parse_with_options("[*.{js,ts}]\n[very_long_name]\n[{a,b,c}{d,e}]", &options);
+    assert!(editor_config.sections()[0].matcher.is_some());
+    assert!(matches!(
+        &diagnostics[..],
+        [
+            Diagnostic { line: 2, kind: DiagnosticKind::InvalidGlob { reason: too_long, .. } },
+            Diagnostic { line: 3, kind: DiagnosticKind::InvalidGlob { reason: too_many, .. } },
+        ] if too_long == "pattern is longer than 12 characters"
+            && too_many == "pattern expands to more than 4 alternatives"
+    ));
+}
+
+#[cfg(feature = "spec-strict")]
+#[test]
+fn spec_strict_limits() {
+    let source = format!(
+        "[{}]\n[*]\n{}=x\nmax_line_length = 80\nmax_line_length = {}",
+        "a".repeat(1025),
+        "k".repeat(1025),
+        "1".repeat(4097)
+    );
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(&source);
+    assert!(editor_config.sections()[0].matcher.is_none());
+    assert_eq!(diagnostics.len(), 1);
+    let properties = &editor_config.sections()[1].properties;
+    // The overlong value is ignored rather than rejected.
+    assert_eq!(properties.max_line_length, Value(MaxLineLength::Number(80)));
+}
+
+#[cfg(feature = "spec-strict")]
+#[test]
+fn spec_strict_inline_comments() {
+    let options = ParseOptions { inline_comments: true, ..ParseOptions::default() };
+    let (editor_config, _) = EditorConfig::parse_with_options("[*]\na = b # c", &options);
+    assert_eq!(
+        editor_config.sections()[0].unknown_properties,
+        [("a".to_string(), "b # c".to_string())]
+    );
+}
+
+#[test]
+fn match_options() {
+    let source = "[*.rs]\nindent_size = 2\n[a\\b{,.txt}]\nindent_size = 3\n[[!x]]\nindent_size = 4";
+    let resolve = |options: MatchOptions, path: &str| {
+        let options = ParseOptions { match_options: options, ..ParseOptions::default() };
+        EditorConfig::parse_with_options(source, &options).0.resolve(Path::new(path)).indent_size
+    };
+    let default = MatchOptions::default();
+    assert_eq!(resolve(default, "ab"), Value(IndentSize::Number(3)));
+    assert_eq!(resolve(default, "ab.txt"), Value(IndentSize::Number(3)));
+    assert_eq!(resolve(default, "/"), EditorConfigProperty::None);
+
+    let options = MatchOptions { literal_separator: false, ..default };
+    assert_eq!(resolve(options, "/"), Value(IndentSize::Number(4)));
+    let options = MatchOptions { backslash_escape: false, ..default };
+    assert_eq!(resolve(options, "ab"), EditorConfigProperty::None);
+    let options = MatchOptions { empty_alternates: false, ..default };
+    assert_eq!(resolve(options, "ab"), EditorConfigProperty::None);
+    assert_eq!(resolve(options, "ab.txt"), Value(IndentSize::Number(3)));
+}
+
+#[test]
+fn posix_classes() {
+    let source = "[[[:digit:]].txt]\nindent_size = 2\n[[![:alpha:][:punct:]]]\nindent_size = 3";
+    let resolve = |posix_classes: bool, path: &str| {
+        let match_options = MatchOptions { posix_classes, ..MatchOptions::default() };
+        let options = ParseOptions { match_options, ..ParseOptions::default() };
+        EditorConfig::parse_with_options(source, &options).0.resolve(Path::new(path)).indent_size
+    };
+    assert_eq!(resolve(false, "1.txt"), EditorConfigProperty::None);
+    assert_eq!(resolve(false, "[.txt"), EditorConfigProperty::None);
+    if cfg!(feature = "spec-strict") {
+        assert_eq!(resolve(true, "1.txt"), EditorConfigProperty::None);
+    } else {
+        assert_eq!(resolve(true, "1.txt"), Value(IndentSize::Number(2)));
+        assert_eq!(resolve(true, "a.txt"), EditorConfigProperty::None);
+        assert_eq!(resolve(true, "1"), Value(IndentSize::Number(3)));
+        assert_eq!(resolve(true, "a"), EditorConfigProperty::None);
+        assert_eq!(resolve(true, "!"), EditorConfigProperty::None);
+    }
+}
+
+#[test]
+fn section_lookup() {
+    let editor_config =
+        EditorConfig::parse("[*]\n[*.rs]\nindent_size = 4\n[*.rs]\nindent_size = 2");
+    assert_eq!(editor_config.section_index_of("*.rs"), Some(1));
+    assert_eq!(
+        editor_config.find_section("*.rs").unwrap().properties.indent_size,
+        Value(IndentSize::Number(4))
+    );
+    assert_eq!(
+        editor_config.section(2).unwrap().properties.indent_size,
+        Value(IndentSize::Number(2))
+    );
+    assert!(editor_config.section(3).is_none());
+    assert!(editor_config.find_section("*.RS").is_none());
+    assert_eq!(editor_config.section_index_of("**"), None);
 }
 
 #[test]
@@ -60,10 +215,99 @@ fn values() {
     assert_eq!(properties.insert_final_newline, Value(true));
     assert_eq!(properties.end_of_line, Value(EndOfLine::Lf));
     assert_eq!(properties.indent_style, Value(IndentStyle::Space));
-    assert_eq!(properties.indent_size, Value(2));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(2)));
     assert_eq!(properties.max_line_length, Value(MaxLineLength::Number(80)));
 }
 
+#[test]
+fn parse_body() {
+    let section = EditorConfigSection::parse_body(
+        "*.rs",
+        "
+        # comment
+        indent_style = tab
+        indent_size = 4
+        [*.md]
+        tab_width = 8
+    ",
+    );
+    assert_eq!(section.name, "*.rs");
+    assert!(section.matcher.as_ref().is_some_and(|matcher| matcher.is_match("main.rs")));
+    assert_eq!(
+        section.properties,
+        EditorConfigProperties {
+            indent_style: Value(IndentStyle::Tab),
+            indent_size: Value(IndentSize::Number(4)),
+            tab_width: Value(8),
+            ..EditorConfigProperties::default()
+        }
+    );
+}
+
+#[test]
+fn specificity() {
+    let specificity = |name: &str| {
+        EditorConfigSection { name: name.to_string(), ..Default::default() }.specificity()
+    };
+    assert_eq!(specificity("Makefile"), Specificity::Literal);
+    assert_eq!(specificity("src/{lib,main}.rs"), Specificity::Literal);
+    assert_eq!(specificity("\\*.rs"), Specificity::Literal);
+    assert_eq!(specificity("*.rs"), Specificity::Extension);
+    assert_eq!(specificity("docs/*.{md,txt}"), Specificity::Extension);
+    assert_eq!(specificity("*"), Specificity::Wildcard);
+    assert_eq!(specificity("**/*.rs"), Specificity::Wildcard);
+    assert_eq!(specificity("[Mm]akefile"), Specificity::Wildcard);
+    assert!(Specificity::Literal > Specificity::Extension);
+    assert!(Specificity::Extension > Specificity::Wildcard);
+}
+
+#[test]
+fn pattern_kind() {
+    let section = |name: &str| EditorConfigSection { name: name.to_string(), ..Default::default() };
+    assert_eq!(section("*").pattern_kind(), PatternKind::MatchAll);
+    assert_eq!(section("**/*").pattern_kind(), PatternKind::MatchAll);
+    assert_eq!(section("*.{js,ts}").pattern_kind(), PatternKind::ExtensionOnly);
+    assert_eq!(section("{package.json,Makefile}").pattern_kind(), PatternKind::FilenameOnly);
+    assert_eq!(section("/*").pattern_kind(), PatternKind::DirectoryAnchored);
+    assert_eq!(section("test_*.py").pattern_kind(), PatternKind::Other);
+    assert!(section("src/**/*.rs").applies_only_to_directories());
+    assert!(!section("*.rs").applies_only_to_directories());
+
+    // File name sections are indexed by name, and still match in any directory.
+    let editor_config = EditorConfig::parse(
+        "[{package.json,Makefile}]\nindent_style = tab\n[*.json]\nindent_size = 2\n[*]\ntab_width = 8",
+    );
+    let resolved = editor_config.resolve_with_matches(Path::new("a/package.json"));
+    assert_eq!(resolved.matched_sections, 3);
+    assert_eq!(resolved.properties.indent_style, Value(IndentStyle::Tab));
+    assert_eq!(editor_config.resolve_with_matches(Path::new("Makefile")).matched_sections, 2);
+    assert_eq!(editor_config.resolve_with_matches(Path::new("a.json")).matched_sections, 2);
+}
+
+#[test]
+fn section_matches() {
+    let editor_config = EditorConfig::parse("[*.rs]\n[[z-a]]");
+    let [rs, invalid] = editor_config.sections() else { panic!() };
+    assert!(rs.matches(Path::new("src/main.rs")));
+    assert!(!rs.matches(Path::new("main.js")));
+    assert_eq!(rs.try_matches(Path::new("main.rs")), Ok(true));
+    assert!(!invalid.matches(Path::new("a")));
+    let err = invalid.try_matches(Path::new("a")).unwrap_err();
+    assert_eq!(err.pattern, "[z-a]");
+    assert_eq!(invalid.glob_error.as_ref(), Some(&err));
+    assert_eq!(err.id(), "invalid-glob");
+    assert_eq!(diagnostic::render(&err, diagnostic::english), err.to_string());
+
+    // The error from compiling with the parse options is kept, rather than compiling again.
+    let options = ParseOptions {
+        glob_limits: GlobLimits { max_pattern_len: 12, ..GlobLimits::default() },
+        ..ParseOptions::default()
+    };
+    let (editor_config, _) = EditorConfig::parse_with_options("[very_long_name]", &options);
+    let err = editor_config.sections()[0].try_matches(Path::new("very_long_name")).unwrap_err();
+    assert_eq!(err.kind, GlobErrorKind::TooLong { max: 12 });
+}
+
 #[test]
 fn max_line_length_off() {
     let editor_config = EditorConfig::parse(
@@ -76,6 +320,219 @@ fn max_line_length_off() {
     assert_eq!(properties.max_line_length, Value(MaxLineLength::Off));
 }
 
+#[test]
+fn property_combinators() {
+    let unset: EditorConfigProperty<usize> = EditorConfigProperty::Unset;
+    assert!(Value(4).is_set());
+    assert!(!unset.is_set());
+    assert_eq!(Value(4).as_option(), Some(&4));
+    assert_eq!(unset.as_option(), None);
+    assert_eq!(EditorConfigProperty::None.unwrap_or(8), 8);
+    assert_eq!(Value(4).map(|n| n * 2), Value(8));
+    assert_eq!(unset.clone().map(|n| n * 2), EditorConfigProperty::Unset);
+    assert_eq!(unset.or(Value(2)), Value(2));
+    assert_eq!(Value(4).or(Value(2)), Value(4));
+}
+
+#[test]
+fn property_conversions() {
+    let unset: EditorConfigProperty<usize> = EditorConfigProperty::Unset;
+    assert_eq!(Option::from(Value(4)), Some(4));
+    assert_eq!(Option::<usize>::from(unset.clone()), None);
+    assert_eq!(Value(4).into_tristate(), Some(Some(4)));
+    assert_eq!(unset.into_tristate(), Some(None));
+    assert_eq!(EditorConfigProperty::<usize>::None.into_tristate(), None);
+}
+
+#[test]
+fn value_parsers() {
+    assert_eq!(value::parse_bool("TRUE"), Value(true));
+    assert_eq!(value::parse_bool("yes"), EditorConfigProperty::None);
+    assert_eq!(value::parse_indent_size("4"), Value(IndentSize::Number(4)));
+    assert_eq!(value::parse_indent_size("Unset"), EditorConfigProperty::Unset);
+    assert_eq!(value::parse_indent_style("tab"), Value(IndentStyle::Tab));
+    assert_eq!(value::parse_end_of_line("CRLF"), Value(EndOfLine::Crlf));
+    assert_eq!(value::parse_charset("utf-8-bom"), Value(Charset::Utf8bom));
+    assert_eq!(value::parse_max_line_length("off"), Value(MaxLineLength::Off));
+    assert_eq!(value::parse_max_line_length("-1"), EditorConfigProperty::None);
+}
+
+#[test]
+fn value_display_and_from_str() {
+    assert_eq!(Charset::Utf8bom.to_string(), "utf-8-bom");
+    assert_eq!("UTF-8-BOM".parse(), Ok(Charset::Utf8bom));
+    assert_eq!("crlf".parse(), Ok(EndOfLine::Crlf));
+    assert_eq!("space".parse(), Ok(IndentStyle::Space));
+    assert_eq!(MaxLineLength::Number(80).to_string(), "80");
+    assert_eq!("off".parse(), Ok(MaxLineLength::Off));
+    let error = "wide".parse::<MaxLineLength>().unwrap_err();
+    assert_eq!(error.to_string(), "invalid value `wide`");
+    assert!("unset".parse::<IndentStyle>().is_err());
+    for value in ["lf", "cr", "crlf"] {
+        assert_eq!(value.parse::<EndOfLine>().unwrap().to_string(), value);
+    }
+}
+
+#[test]
+fn nonstandard_values() {
+    assert_eq!(value::parse_end_of_line("Native"), Value(EndOfLine::Other("native".to_string())));
+    assert_eq!(value::parse_end_of_line(""), EditorConfigProperty::None);
+    assert_eq!(value::parse_end_of_line("lf lf"), EditorConfigProperty::None);
+    let source = "[*]\nindent_style = Smart\nend_of_line = native\ncharset = utf-16\n";
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(source);
+    assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.line).collect::<Vec<_>>(), [2, 3, 4]);
+    assert_eq!(
+        diagnostics[1].kind,
+        DiagnosticKind::NonstandardValue {
+            key: "end_of_line".to_string(),
+            value: "native".to_string()
+        }
+    );
+    assert_eq!(diagnostics[1].code(), Some("EC012"));
+    assert_eq!(EditorConfig::try_parse(source).unwrap_err().len(), 3);
+    assert_eq!(
+        EditorConfig::builder().section("*").unwrap().property("indent_style", "tabs").unwrap_err(),
+        BuildError::InvalidValue { key: "indent_style".to_string(), value: "tabs".to_string() }
+    );
+    let properties = editor_config.resolve(Path::new("a.txt"));
+    assert_eq!(properties.indent_style, Value(IndentStyle::Other("smart".to_string())));
+    assert_eq!(properties.end_of_line, Value(EndOfLine::Other("native".to_string())));
+    assert_eq!(properties.charset, Value(Charset::Other("utf-16".to_string())));
+    assert_eq!(editor_config.to_string(), source.to_ascii_lowercase());
+}
+
+#[test]
+fn raw_values() {
+    let editor_config =
+        EditorConfig::parse("[*]\nEnd_Of_Line = LF\nindent_size = two\nindent_size = 4\nfoo = Bar");
+    let section = &editor_config.sections()[0];
+    assert_eq!(section.properties.end_of_line, Value(EndOfLine::Lf));
+    assert_eq!(section.raw_value(PropertyKey::EndOfLine), Some("LF"));
+    assert_eq!(section.raw_value(PropertyKey::IndentSize), Some("4"));
+    assert_eq!(section.raw_value(PropertyKey::Charset), None);
+    assert_eq!(
+        section.raw_properties,
+        [
+            ("end_of_line".to_string(), "LF".to_string()),
+            ("indent_size".to_string(), "two".to_string()),
+            ("indent_size".to_string(), "4".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn resolve_unknown_properties() {
+    let editor_config = EditorConfig::parse(
+        "[*]\nij_continuation_indent_size = 8\nquote_type = single\n[*.md]\nQuote_Type = unset\n[a.md]\nij_continuation_indent_size = 4",
+    );
+    let properties = editor_config.resolve(Path::new("a.md"));
+    assert_eq!(properties.get_raw("IJ_continuation_indent_size"), Some("4"));
+    assert_eq!(properties.get_raw("quote_type"), None);
+    assert_eq!(editor_config.resolve(Path::new("b.rs")).get_raw("quote_type"), Some("single"));
+    assert_eq!(properties.get_raw("indent_size"), None);
+}
+
+#[test]
+fn property_registry() {
+    let mut registry = PropertyRegistry::new();
+    registry.register("print_width", |value| value.parse::<usize>().ok());
+    let editor_config = EditorConfig::parse(
+        "[*]\nprint_width = 80\n[*.md]\nPrint_Width = wide\n[*.txt]\nprint_width = unset",
+    );
+    let properties = editor_config.resolve(Path::new("a.rs"));
+    assert_eq!(registry.get::<usize>(&properties, "PRINT_WIDTH"), Value(80));
+    assert_eq!(registry.get::<u8>(&properties, "print_width"), EditorConfigProperty::None);
+    assert_eq!(registry.get::<usize>(&properties, "tab_width"), EditorConfigProperty::None);
+    let properties = editor_config.resolve(Path::new("a.md"));
+    assert_eq!(registry.get::<usize>(&properties, "print_width"), EditorConfigProperty::None);
+    let properties = editor_config.resolve(Path::new("a.txt"));
+    assert_eq!(registry.get::<usize>(&properties, "print_width"), EditorConfigProperty::None);
+    let section = &editor_config.sections()[2].properties;
+    assert_eq!(registry.get::<usize>(section, "print_width"), EditorConfigProperty::Unset);
+}
+
+#[test]
+fn property_registry_capturing_parser() {
+    let max = 100;
+    let mut registry = PropertyRegistry::new();
+    registry
+        .register("print_width", move |value| value.parse::<usize>().ok().filter(|w| *w <= max));
+    let editor_config = EditorConfig::parse("[*.rs]\nprint_width = 80\n[*.md]\nprint_width = 120");
+    let properties = editor_config.resolve(Path::new("a.rs"));
+    assert_eq!(registry.get::<usize>(&properties, "print_width"), Value(80));
+    let properties = editor_config.resolve(Path::new("a.md"));
+    assert_eq!(registry.get::<usize>(&properties, "print_width"), EditorConfigProperty::None);
+}
+
+#[cfg(all(feature = "extended-properties", feature = "spec-strict"))]
+#[test]
+fn spec_strict_extended_properties() {
+    let editor_config = EditorConfig::parse("[*]\nquote_type = single");
+    let properties = editor_config.resolve(Path::new("a.rs"));
+    assert_eq!(properties.get_raw("quote_type"), Some("single"));
+    assert_eq!(properties.extended(), editorconfig_parser::extended::ExtendedProperties::default());
+}
+
+#[cfg(all(feature = "extended-properties", not(feature = "spec-strict")))]
+#[test]
+fn extended_properties() {
+    use editorconfig_parser::extended::{ExtendedProperties, QuoteType, SpacesAroundOperators};
+
+    let editor_config = EditorConfig::parse(
+        "[*]\nquote_type = Single\ncurly_bracket_next_line = maybe\n[*.js]\nquote_type = unset\nspaces_around_operators = hybrid\ncontinuation_indent_size = 8",
+    );
+    let extended = editor_config.resolve(Path::new("a.rs")).extended();
+    assert_eq!(extended.quote_type, Value(QuoteType::Single));
+    assert_eq!(extended.curly_bracket_next_line, EditorConfigProperty::None);
+    assert_eq!(
+        editor_config.resolve(Path::new("a.js")).extended(),
+        ExtendedProperties {
+            spaces_around_operators: Value(SpacesAroundOperators::Hybrid),
+            continuation_indent_size: Value(8),
+            ..ExtendedProperties::default()
+        }
+    );
+    let section = editor_config.sections()[1].properties.extended();
+    assert_eq!(section.quote_type, EditorConfigProperty::Unset);
+}
+
+#[test]
+fn intellij_properties() {
+    let editor_config = EditorConfig::parse(
+        "[*]\nij_visual_guides = 80,120\nij_any_wrap_long_lines = true\nij_java_imports_layout = *,|,$*\nij_http-request_call_parameters_wrap = normal\nquote_type = single",
+    );
+    let ij = editor_config.resolve(Path::new("A.java")).intellij();
+    assert_eq!(ij.general.get("visual_guides").map(String::as_str), Some("80,120"));
+    assert_eq!(ij.get("java", "imports_layout"), Some("*,|,$*"));
+    assert_eq!(ij.get("java", "wrap_long_lines"), Some("true"));
+    assert_eq!(ij.get("kotlin", "imports_layout"), None);
+    assert_eq!(ij.get("http-request", "call_parameters_wrap"), Some("normal"));
+    assert_eq!(ij.languages.keys().collect::<Vec<_>>(), ["any", "http-request", "java"]);
+}
+
+#[test]
+fn resolve_traced() {
+    let editor_config = EditorConfig::parse(
+        "root = true\n\n[*]\nindent_style = space\nindent_size = 4\ncharset = utf-8\n\n[*.md]\nindent_size = 2\ncharset = unset",
+    );
+    let traced = editor_config.resolve_traced(Path::new("README.md"));
+    assert_eq!(traced.properties, editor_config.resolve(Path::new("README.md")));
+    assert_eq!(
+        traced.source(PropertyKey::IndentStyle),
+        Some(&PropertySource { section: 0, pattern: "*".to_string(), line: Some(3) })
+    );
+    assert_eq!(
+        traced.source(PropertyKey::IndentSize),
+        Some(&PropertySource { section: 1, pattern: "*.md".to_string(), line: Some(8) })
+    );
+    assert_eq!(traced.source(PropertyKey::Charset), None);
+    assert_eq!(traced.sources.len(), 2);
+
+    let built = EditorConfig::builder().section("*").unwrap().property("tab_width", "8").unwrap();
+    let traced = built.build().resolve_traced(Path::new("a.rs"));
+    assert_eq!(traced.source(PropertyKey::TabWidth).unwrap().line, None);
+}
+
 #[test]
 fn resolve() {
     let editor_config = EditorConfig::parse(
@@ -116,7 +573,7 @@ fn resolve() {
     assert_eq!(all.insert_final_newline, Value(true));
     assert_eq!(all.end_of_line, Value(EndOfLine::Lf));
     assert_eq!(all.indent_style, Value(IndentStyle::Space));
-    assert_eq!(all.indent_size, Value(2));
+    assert_eq!(all.indent_size, Value(IndentSize::Number(2)));
     assert_eq!(all.max_line_length, Value(MaxLineLength::Number(80)));
 
     let properties = editor_config.resolve(&path.join("file.foo"));
@@ -124,14 +581,14 @@ fn resolve() {
     assert_eq!(properties.insert_final_newline, Value(false));
     assert_eq!(properties.end_of_line, Value(EndOfLine::Crlf));
     assert_eq!(properties.indent_style, Value(IndentStyle::Tab));
-    assert_eq!(properties.indent_size, Value(4));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
     assert_eq!(properties.max_line_length, Value(MaxLineLength::Number(100)));
 
     for ext in ["ts", "tsx", "js", "jsx", "mts", "cts"] {
         assert_eq!(
             editor_config.resolve(&path.join("file").with_extension(ext)),
             EditorConfigProperties {
-                indent_size: Value(8),
+                indent_size: Value(IndentSize::Number(8)),
                 max_line_length: Value(MaxLineLength::Number(120)),
                 ..all.clone()
             }
@@ -179,6 +636,28 @@ fn unset() {
     let path = Path::new("/").join("file.foo");
     let properties = editor_config.resolve(&path);
     assert_eq!(properties, EditorConfigProperties::default());
+
+    let resolved = editor_config.resolve_with_matches(&path);
+    assert_eq!(resolved.properties, EditorConfigProperties::default());
+    assert_eq!(resolved.matched_sections, 2);
+    assert!(resolved.is_matched());
+}
+
+#[test]
+fn resolve_with_matches() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+    ",
+    );
+    let resolved = editor_config.resolve_with_matches(Path::new("main.rs"));
+    assert_eq!(resolved.properties.indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(resolved.matched_sections, 1);
+
+    let resolved = editor_config.resolve_with_matches(Path::new("main.ts"));
+    assert_eq!(resolved, Resolved::default());
+    assert!(!resolved.is_matched());
 }
 
 #[test]
@@ -201,18 +680,18 @@ fn resolve_with_cwd() {
 
     // Absolute path should be resolved relative to cwd
     let properties = editor_config.resolve(Path::new("/project/file.ts"));
-    assert_eq!(properties.indent_size, Value(4));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
 
     let properties = editor_config.resolve(Path::new("/project/src/file.ts"));
-    assert_eq!(properties.indent_size, Value(8));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(8)));
 
     // Path not under cwd should still work (uses path as-is)
     let properties = editor_config.resolve(Path::new("/other/file.ts"));
-    assert_eq!(properties.indent_size, Value(4));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
 
     // Relative path should work as before
     let properties = editor_config.resolve(Path::new("file.ts"));
-    assert_eq!(properties.indent_size, Value(4));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
 }
 
 #[test]
@@ -227,5 +706,1672 @@ fn resolve_with_cwd_pathbuf() {
     .with_cwd(&cwd);
 
     let properties = editor_config.resolve(&cwd.join("main.rs"));
-    assert_eq!(properties.indent_size, Value(4));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+}
+
+#[test]
+fn properties_builder() {
+    let properties =
+        EditorConfigProperties::builder().indent_style(IndentStyle::Space).indent_size(2).build();
+    assert_eq!(
+        properties,
+        EditorConfigProperties {
+            indent_style: Value(IndentStyle::Space),
+            indent_size: Value(IndentSize::Number(2)),
+            ..EditorConfigProperties::default()
+        }
+    );
+    let properties = EditorConfigProperties::builder()
+        .indent_size(2)
+        .indent_size_tab()
+        .unset(PropertyKey::Charset)
+        .max_line_length(MaxLineLength::Off)
+        .build();
+    let editor_config =
+        EditorConfig::parse("[*]\nindent_size = tab\ncharset = unset\nmax_line_length = off");
+    assert_eq!(properties, editor_config.sections()[0].properties);
+}
+
+#[test]
+fn merge_properties() {
+    let parent = EditorConfigProperties::builder().indent_size(4).charset(Charset::Utf8).build();
+    let child = EditorConfigProperties::builder()
+        .indent_size(2)
+        .unset(PropertyKey::Charset)
+        .end_of_line(EndOfLine::Lf)
+        .build();
+    let merged = parent.merged(&child);
+    assert_eq!(
+        merged,
+        EditorConfigProperties::builder().indent_size(2).end_of_line(EndOfLine::Lf).build()
+    );
+    let mut properties = parent.clone();
+    properties.override_with(&child);
+    assert_eq!(properties, merged);
+    assert_eq!(parent.indent_size, Value(IndentSize::Number(4)));
+}
+
+#[test]
+fn builder() {
+    let editor_config = EditorConfig::builder()
+        .root(true)
+        .section("*.rs")
+        .and_then(|builder| builder.property("indent_size", "4"))
+        .and_then(|builder| builder.property("unknown_key", "anything"))
+        .unwrap()
+        .build();
+    assert!(editor_config.root());
+    assert_eq!(
+        editor_config.resolve(Path::new("main.rs")).indent_size,
+        Value(IndentSize::Number(4))
+    );
+
+    // A clone built and resolved earlier does not leave stale merged properties behind.
+    let builder =
+        EditorConfig::builder().section("*.rs").unwrap().property("indent_size", "2").unwrap();
+    let earlier = builder.clone().build();
+    assert_eq!(earlier.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(2)));
+    let later = builder.property("indent_size", "4").unwrap().build();
+    assert_eq!(later.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(4)));
+
+    assert!(matches!(
+        EditorConfig::builder().section("[z-a].rs").unwrap_err(),
+        BuildError::InvalidGlob { pattern, .. } if pattern == "[z-a].rs"
+    ));
+
+    assert_eq!(
+        EditorConfig::builder().section("*").unwrap().property("indent_size", "two").unwrap_err(),
+        BuildError::InvalidValue { key: "indent_size".to_string(), value: "two".to_string() }
+    );
+
+    assert_eq!(
+        EditorConfig::builder().property("indent_size", "2").unwrap_err(),
+        BuildError::NoSection { key: "indent_size".to_string() }
+    );
+}
+
+#[test]
+fn diagnostic_messages() {
+    let error =
+        BuildError::InvalidValue { key: "indent_size".to_string(), value: "two".to_string() };
+    assert_eq!(error.id(), "invalid-value");
+    assert_eq!(error.to_string(), "invalid value `two` for `indent_size`");
+
+    let french =
+        |id: &str| (id == "invalid-value").then_some("valeur `{value}` invalide pour `{key}`");
+    assert_eq!(diagnostic::render(&error, french), "valeur `two` invalide pour `indent_size`");
+
+    let error = BuildError::NoSection { key: "indent_size".to_string() };
+    assert_eq!(
+        diagnostic::render(&error, french),
+        "property `indent_size` must be inside a section"
+    );
+
+    // Placeholders are only substituted in the template, not in values.
+    let error =
+        BuildError::InvalidValue { key: "indent_size".to_string(), value: "{key}".to_string() };
+    assert_eq!(error.to_string(), "invalid value `{key}` for `indent_size`");
+}
+
+#[cfg(feature = "ignore")]
+#[test]
+fn to_overrides() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [docs/*.md]
+        indent_size = 2
+    ",
+    );
+    let overrides = editor_config.to_overrides("/project").unwrap();
+    assert!(overrides.matched("/project/src/main.rs", false).is_whitelist());
+    assert!(overrides.matched("/project/docs/README.md", false).is_whitelist());
+    assert!(overrides.matched("/project/README.md", false).is_ignore());
+    assert!(overrides.matched("/project/package.json", false).is_ignore());
+}
+
+#[test]
+fn resolve_anchored() {
+    let editor_config = EditorConfig::parse(
+        "
+        [/build/*.js]
+        indent_size = 2
+    ",
+    )
+    .with_cwd("/project");
+
+    let properties = editor_config.resolve(Path::new("/project/build/main.js"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(2)));
+
+    let properties = editor_config.resolve(Path::new("/project/src/build/main.js"));
+    assert_eq!(properties, EditorConfigProperties::default());
+
+    let properties = editor_config.resolve(Path::new("/project/build/dist/main.js"));
+    assert_eq!(properties, EditorConfigProperties::default());
+
+    let properties = editor_config.resolve(Path::new("/other/build/main.js"));
+    assert_eq!(properties, EditorConfigProperties::default());
+}
+
+#[cfg(windows)]
+#[test]
+fn resolve_windows_paths() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [src/**]
+        indent_size = 8
+    ",
+    );
+
+    let properties = editor_config.resolve(Path::new("main.rs"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+
+    let properties = editor_config.resolve(Path::new("src\\bin\\main.rs"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(8)));
+
+    let properties = editor_config.resolve(Path::new("C:\\repo\\tests\\main.rs"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+
+    let editor_config = editor_config.with_cwd("C:\\repo");
+    for path in ["C:\\repo\\src\\main.rs", "C:\\repo/src\\main.rs", "C:/repo/src/main.rs"] {
+        let properties = editor_config.resolve(Path::new(path));
+        assert_eq!(properties.indent_size, Value(IndentSize::Number(8)), "{path}");
+    }
+
+    let editor_config = editor_config.with_cwd("\\\\server\\share\\repo");
+    let properties = editor_config.resolve(Path::new("\\\\server\\share\\repo\\src\\main.rs"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(8)));
+}
+
+#[test]
+fn resolve_case_insensitive() {
+    let editor_config = EditorConfig::parse(
+        "
+        [Makefile]
+        indent_style = tab
+
+        [*.{TS,tsx}]
+        indent_size = 4
+    ",
+    );
+    assert!(!editor_config.case_insensitive());
+    assert_eq!(editor_config.resolve(Path::new("makefile")), EditorConfigProperties::default());
+
+    let editor_config = editor_config.with_case_insensitive(true);
+    assert!(editor_config.case_insensitive());
+    assert_eq!(editor_config.resolve(Path::new("makefile")).indent_style, Value(IndentStyle::Tab));
+    assert_eq!(
+        editor_config.resolve(Path::new("src/MAKEFILE")).indent_style,
+        Value(IndentStyle::Tab)
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("index.ts")).indent_size,
+        Value(IndentSize::Number(4))
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("index.TSX")).indent_size,
+        Value(IndentSize::Number(4))
+    );
+}
+
+#[test]
+fn case_mismatch() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*]
+        indent_size = 2
+
+        [*.ts]
+        indent_size = 4
+    ",
+    );
+    assert_eq!(editor_config.case_mismatch(Path::new("foo.ts")), None);
+
+    let mismatch = editor_config.case_mismatch(Path::new("Foo.TS")).unwrap();
+    assert_eq!(mismatch.sections, ["*.ts"]);
+    assert_eq!(
+        diagnostic::render(&mismatch, diagnostic::english),
+        "`Foo.TS` only matches [*.ts] when ignoring case"
+    );
+
+    let editor_config = editor_config.with_case_insensitive(true);
+    assert_eq!(editor_config.case_mismatch(Path::new("Foo.TS")), None);
+}
+
+#[test]
+fn resolve_directory() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*]
+        indent_size = 2
+
+        [*.rs]
+        indent_size = 4
+
+        [src/*]
+        indent_style = tab
+
+        [lib/**]
+        tab_width = 8
+
+        [?]
+        max_line_length = 80
+    ",
+    )
+    .with_cwd("/project");
+
+    // The same sections editorconfig-core applies to a path with a trailing `/`.
+    assert_eq!(
+        editor_config.resolve_directory(Path::new("/project/src")),
+        EditorConfigProperties {
+            indent_size: Value(IndentSize::Number(2)),
+            indent_style: Value(IndentStyle::Tab),
+            ..EditorConfigProperties::default()
+        }
+    );
+    assert_eq!(
+        editor_config.resolve_directory(Path::new("/project/lib")),
+        EditorConfigProperties {
+            indent_size: Value(IndentSize::Number(2)),
+            tab_width: Value(8),
+            ..EditorConfigProperties::default()
+        }
+    );
+    assert_eq!(
+        editor_config.resolve_directory(Path::new("/project/lib/nested")),
+        EditorConfigProperties {
+            indent_size: Value(IndentSize::Number(2)),
+            tab_width: Value(8),
+            ..EditorConfigProperties::default()
+        }
+    );
+    assert_eq!(
+        editor_config.resolve_directory(Path::new("/project")),
+        EditorConfigProperties {
+            indent_size: Value(IndentSize::Number(2)),
+            ..EditorConfigProperties::default()
+        }
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_non_utf8_paths() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [src/*]
+        indent_style = tab
+    ",
+    )
+    .with_cwd(OsStr::from_bytes(b"/caf\xe9"));
+
+    let properties = editor_config.resolve(Path::new(OsStr::from_bytes(b"/caf\xe9/src/\xff.rs")));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(properties.indent_style, Value(IndentStyle::Tab));
+
+    let properties = editor_config.resolve(Path::new(OsStr::from_bytes(b"src/\xff.txt")));
+    assert_eq!(properties.indent_style, Value(IndentStyle::Tab));
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_backslash_file_names() {
+    // `\` is a file name character outside Windows, not a separator.
+    let editor_config =
+        EditorConfig::parse("[foo/*.rs]\nindent_size = 2\n[foo\\\\bar.rs]\nindent_size = 4");
+    let properties = editor_config.resolve(Path::new("foo\\bar.rs"));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+    assert!(!EditorConfigGlob::new("foo/*.rs").unwrap().is_match("foo\\bar.rs"));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn testing_harness() {
+    use editorconfig_parser::{assert_resolves, testing};
+
+    assert_resolves!("[*.rs]\nindent_size = 4", "src/main.rs", "indent_size = 4");
+    assert!(
+        testing::check_resolves("[*.rs]\nindent_size = 4", "main.js", "indent_size = 4").is_err()
+    );
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    assert_eq!(testing::run_fixtures(&fixtures).unwrap(), Vec::<String>::new());
+
+    let cancel = AtomicBool::new(true);
+    let err = testing::run_fixtures_with_cancel(&fixtures, &cancel).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn corpus() {
+    use editorconfig_parser::corpus::{self, CorpusStats, GlobFeature};
+
+    let stats =
+        corpus::ingest(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")).unwrap();
+    assert_eq!(stats.files, 1);
+    assert_eq!(stats.sections, 3);
+    let cancel = AtomicBool::new(true);
+    let err = corpus::ingest_with_cancel(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"),
+        &cancel,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+
+    let mut stats = CorpusStats::default();
+    stats.add(
+        Path::new("a/.editorconfig"),
+        b"root = true\n[*.{js,ts}]\nquote_type = single\nindent_size = two\n[src/**]\n[[z-a]]",
+    );
+    assert_eq!(stats.unknown_keys.get("quote_type"), Some(&1));
+    assert_eq!(stats.invalid_values.get("indent_size"), Some(&1));
+    assert_eq!(stats.glob_features.get(&GlobFeature::Star), Some(&1));
+    assert_eq!(stats.glob_features.get(&GlobFeature::Alternation), Some(&1));
+    assert_eq!(stats.glob_features.get(&GlobFeature::DoubleStar), Some(&1));
+    assert_eq!(stats.glob_features.get(&GlobFeature::Path), Some(&1));
+    assert_eq!(stats.pattern_kinds.get(&PatternKind::ExtensionOnly), Some(&1));
+    assert_eq!(stats.pattern_kinds.get(&PatternKind::DirectoryAnchored), Some(&1));
+    assert_eq!(stats.parse_failures.len(), 1);
+
+    // Statistics follow the parser: a lone `\r` ends a line, preamble pairs are ignored,
+    // and `quote_type = double # comment` is one unknown pair.
+    let mut stats = CorpusStats::default();
+    stats.add(
+        Path::new(".editorconfig"),
+        b"\xEF\xBB\xBFtab_width = x\r[*]\rindent_size = two\rquote_type = double # comment\rcharset = utf-16\r",
+    );
+    assert_eq!(stats.sections, 1);
+    assert_eq!(stats.unknown_keys.get("quote_type"), Some(&1));
+    assert_eq!(stats.invalid_values.len(), 2);
+    assert_eq!(stats.invalid_values.get("charset"), Some(&1));
+    assert_eq!(stats.invalid_values.get("indent_size"), Some(&1));
+}
+
+#[test]
+fn serialize() {
+    let editor_config = EditorConfig::parse(
+        "
+        root = true
+        # comment
+        [*]
+        indent_style = space
+        indent_size = 2
+        quote_type = single
+
+        [*.md]
+        max_line_length = off
+        indent_size = unset
+        ",
+    );
+    assert_eq!(
+        editor_config.to_string(),
+        "root = true\n\n[*]\nindent_style = space\nindent_size = 2\nquote_type = single\n\n[*.md]\nindent_size = unset\nmax_line_length = off\n"
+    );
+}
+
+#[test]
+fn round_trips() {
+    assert_eq!(EditorConfig::round_trips(""), Ok(()));
+    assert_eq!(
+        EditorConfig::round_trips(
+            "root = true\n[*]\nend_of_line = CRLF\n[Makefile]\nindent_style = tab\n"
+        ),
+        Ok(())
+    );
+    let divergence = RoundTripDivergence {
+        source_line: Some(1),
+        serialized_line: Some(1),
+        kind: RoundTripDivergenceKind::Root,
+    };
+    assert_eq!(divergence.to_string(), "source line 1, serialized line 1: `root` differs");
+}
+
+#[test]
+fn escaped_comment_characters() {
+    let editor_config = EditorConfig::parse(
+        "
+        [foo\\#bar.txt]
+        indent_size = 2
+        [foo\\;bar.txt]
+        indent_size = 3
+        ",
+    );
+    assert_eq!(editor_config.sections()[0].name, "foo\\#bar.txt");
+    assert_eq!(
+        editor_config.resolve(Path::new("foo#bar.txt")).indent_size,
+        Value(IndentSize::Number(2))
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("foo;bar.txt")).indent_size,
+        Value(IndentSize::Number(3))
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("foo\\#bar.txt")).indent_size,
+        EditorConfigProperty::None
+    );
+}
+
+#[test]
+fn resolve_with_config_path() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [/src/*.rs]
+        indent_size = 2
+        ",
+    )
+    .with_cwd("/")
+    .with_config_path("/project/.editorconfig");
+    assert_eq!(editor_config.config_dir(), Some(Path::new("/project")));
+    assert_eq!(
+        editor_config.resolve(Path::new("/project/src/main.rs")).indent_size,
+        Value(IndentSize::Number(2))
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("/project/main.rs")).indent_size,
+        Value(IndentSize::Number(4))
+    );
+    assert_eq!(
+        editor_config.resolve(Path::new("src/main.rs")).indent_size,
+        Value(IndentSize::Number(2))
+    );
+    // Outside the config file's directory, unlike with only a `cwd`.
+    assert!(!editor_config.resolve_with_matches(Path::new("/other/main.rs")).is_matched());
+
+    // A relative config path is relative to the `cwd`, or the current directory.
+    let editor_config = EditorConfig::parse("[*.rs]\nindent_size = 4")
+        .with_cwd("/project")
+        .with_config_path(".editorconfig");
+    assert_eq!(editor_config.config_dir(), Some(Path::new("/project")));
+    assert!(editor_config.resolve_with_matches(Path::new("/project/main.rs")).is_matched());
+    assert!(!editor_config.resolve_with_matches(Path::new("/other/main.rs")).is_matched());
+    let editor_config = EditorConfig::parse("").with_config_path(".editorconfig");
+    assert_eq!(editor_config.config_dir(), Some(std::env::current_dir().unwrap().as_path()));
+}
+
+#[test]
+fn from_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/basic/.editorconfig");
+    let editor_config = EditorConfig::from_file(&path).unwrap();
+    assert_eq!(editor_config.path(), Some(path.as_path()));
+    assert_eq!(editor_config.config_dir(), path.parent());
+    assert!(editor_config.root());
+    assert_eq!(
+        editor_config.resolve(&path.with_file_name("main.rs")).indent_size,
+        Value(IndentSize::Number(4))
+    );
+    assert!(!editor_config.resolve_with_matches(Path::new("/elsewhere/main.rs")).is_matched());
+    assert_eq!(EditorConfig::parse("").path(), None);
+
+    let err = EditorConfig::from_file(path.with_file_name("missing")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn for_directory() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*]
+        indent_size = 2
+
+        [src/**.rs]
+        indent_size = 4
+
+        [/docs/*.md]
+        max_line_length = off
+
+        [{src,docs}/**]
+        tab_width = 8
+        ",
+    )
+    .with_cwd("/project");
+    let names = |dir: &str| {
+        let directory = editor_config.for_directory(Path::new(dir));
+        directory.sections().iter().map(|section| section.name.as_str()).collect::<Vec<_>>()
+    };
+    assert_eq!(names("/project"), ["*", "src/**.rs", "/docs/*.md", "{src,docs}/**"]);
+    assert_eq!(names("/project/src/bin"), ["*", "src/**.rs", "{src,docs}/**"]);
+    assert_eq!(names("docs"), ["*", "/docs/*.md", "{src,docs}/**"]);
+    assert_eq!(names("tests"), ["*", "{src,docs}/**"]);
+
+    let directory = editor_config.for_directory(Path::new("/project/src"));
+    for path in ["/project/src/main.rs", "/project/src/bin/a.rs", "/project/src/README.md"] {
+        let path = Path::new(path);
+        assert_eq!(directory.resolve(path), editor_config.resolve(path), "{}", path.display());
+    }
+}
+
+#[test]
+fn resolve_extension_index() {
+    // Sections indexed by extension must match exactly as without the index.
+    let editor_config = EditorConfig::parse(
+        "
+        [*]
+        [*.ts]
+        [*.{js,jsx}]
+        [src/**/*.d.ts]
+        [{a,b.c}.md]
+        [*.{md,*}]
+        [Makefile]
+        [*.]
+        [lib/]
+        ",
+    );
+    let paths = [
+        "a.ts",
+        "a.d.ts",
+        "src/x/a.d.ts",
+        "a.js",
+        "b.jsx",
+        "a.md",
+        "b.c.md",
+        "c.md",
+        "Makefile",
+        "a.",
+        "lib/a.ts",
+        "a.ts/b",
+        "noext",
+        ".ts",
+    ];
+    for path in paths {
+        let expected = editor_config
+            .sections()
+            .iter()
+            .filter(|section| {
+                section.matcher.as_ref().is_some_and(|matcher| matcher.is_match(path))
+            })
+            .count();
+        let resolved = editor_config.resolve_with_matches(Path::new(path));
+        assert_eq!(resolved.matched_sections, expected, "{path}");
+    }
+}
+
+#[test]
+fn resolve_with_options() {
+    let editor_config = EditorConfig::parse(
+        "
+        [*.rs]
+        indent_size = 4
+
+        [**/other/*.rs]
+        tab_width = 8
+        ",
+    )
+    .with_cwd("/project");
+    let resolve = |path: &str, outside_cwd| {
+        editor_config.resolve_with_options(Path::new(path), &ResolveOptions { outside_cwd })
+    };
+    let resolved = resolve("/other/main.rs", OutsideCwd::MatchAsIs).unwrap();
+    assert_eq!(resolved.properties, editor_config.resolve(Path::new("/other/main.rs")));
+    assert_eq!(resolved.matched_sections, 2);
+
+    let resolved = resolve("/other/main.rs", OutsideCwd::SkipAnchoredSections).unwrap();
+    assert_eq!(resolved.properties.indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(resolved.properties.tab_width, EditorConfigProperty::None);
+
+    let err = resolve("/other/main.rs", OutsideCwd::Error).unwrap_err();
+    assert_eq!(
+        err,
+        ResolveError::OutsideCwd { path: "/other/main.rs".into(), cwd: "/project".into() }
+    );
+    assert_eq!(err.to_string(), "`/other/main.rs` is outside `/project`");
+    assert_eq!(err.code(), Some("EC005"));
+    assert_eq!(resolve("/project/main.rs", OutsideCwd::Error).unwrap().matched_sections, 1);
+}
+
+#[test]
+fn repeated_patterns() {
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(
+        "
+        [*.{js,ts}]
+        indent_size = 2
+        [[z-a]]
+        [*.{js,ts}]
+        tab_width = 4
+        [[z-a]]
+        ",
+    );
+    let invalid_globs = diagnostics
+        .iter()
+        .filter(|diagnostic| matches!(diagnostic.kind, DiagnosticKind::InvalidGlob { .. }));
+    assert_eq!(invalid_globs.map(|diagnostic| diagnostic.line).collect::<Vec<_>>(), [4, 7]);
+    let resolved = editor_config.resolve_with_matches(Path::new("a.ts"));
+    assert_eq!(resolved.matched_sections, 2);
+    assert_eq!(resolved.properties.indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(resolved.properties.tab_width, Value(4));
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn write_configs() {
+    use std::fs;
+
+    use editorconfig_parser::workspace::{ConfigWrite, WriteOptions, write_configs};
+
+    let root = std::env::temp_dir().join(format!("editorconfig-write-{}", std::process::id()));
+    let (a, b) = (root.join("a"), root.join("b"));
+    fs::create_dir_all(&a).unwrap();
+    fs::create_dir_all(&b).unwrap();
+    fs::write(b.join(".editorconfig"), "[*]\nindent_size = 2\n").unwrap();
+    let configs = [
+        (a.clone(), EditorConfig::parse("root = true\n[*.rs]\nindent_size = 4")),
+        (b.clone(), EditorConfig::parse("[*]\nindent_size = 2")),
+    ];
+
+    let writes = write_configs(&configs, &WriteOptions { dry_run: true }).unwrap();
+    assert_eq!(
+        writes,
+        [
+            ConfigWrite { path: a.join(".editorconfig"), changed: true },
+            ConfigWrite { path: b.join(".editorconfig"), changed: false },
+        ]
+    );
+    assert!(!a.join(".editorconfig").exists());
+
+    // Nothing is written if any directory cannot be written to.
+    let missing = [(a.clone(), configs[0].1.clone()), (root.join("missing"), configs[1].1.clone())];
+    assert!(write_configs(&missing, &WriteOptions::default()).is_err());
+    assert!(!a.join(".editorconfig").exists());
+
+    write_configs(&configs, &WriteOptions::default()).unwrap();
+    assert_eq!(
+        fs::read_to_string(a.join(".editorconfig")).unwrap(),
+        "root = true\n\n[*.rs]\nindent_size = 4\n"
+    );
+    assert_eq!(fs::read_dir(&a).unwrap().count(), 1);
+
+    // An existing file that is not UTF-8 is replaced like any other.
+    fs::write(b.join(".editorconfig"), b"[*]\nindent_size = 2 ; \xff\n").unwrap();
+    let writes = write_configs(&configs[1..], &WriteOptions::default()).unwrap();
+    assert!(writes[0].changed);
+    assert_eq!(fs::read_to_string(b.join(".editorconfig")).unwrap(), "[*]\nindent_size = 2\n");
+
+    // The same directory twice is rejected before writing.
+    let twice = [configs[1].clone(), (b.clone(), configs[0].1.clone())];
+    let err = write_configs(&twice, &WriteOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(fs::read_to_string(b.join(".editorconfig")).unwrap(), "[*]\nindent_size = 2\n");
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn try_parse() {
+    assert!(EditorConfig::try_parse("root = true\n[*]\nindent_size = 2\nfoo = bar").is_ok());
+
+    let errors =
+        EditorConfig::try_parse("[*.md\n[*]\n  indent_size = two\n[[z-a].txt]").unwrap_err();
+    assert_eq!(
+        errors[..2],
+        [
+            ParseError {
+                line: 1,
+                column: 1,
+                text: "[*.md".to_string(),
+                kind: ParseErrorKind::InvalidHeader,
+            },
+            ParseError {
+                line: 3,
+                column: 17,
+                text: "two".to_string(),
+                kind: ParseErrorKind::InvalidValue { key: "indent_size".to_string() },
+            },
+        ]
+    );
+    assert!(matches!(
+        &errors[2],
+        ParseError { line: 4, column: 2, text, kind: ParseErrorKind::InvalidGlob { .. } }
+            if text == "[z-a].txt"
+    ));
+    assert_eq!(errors[1].to_string(), "line 3, column 17: invalid value `two` for `indent_size`");
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn settings_matrix() {
+    use editorconfig_parser::matrix::settings_matrix;
+
+    let configs = [
+        EditorConfig::parse("root = true\n[*]\nindent_style = space\nindent_size = 2"),
+        EditorConfig::parse("[*.rs]\nindent_size = 4\n[*.md]\nindent_size = unset"),
+    ];
+    let matrix = settings_matrix(&configs, &["rs", "md"]);
+    assert_eq!(matrix.keys(), ["indent_style", "indent_size"]);
+    let size = matrix.rows[0].get("indent_size").unwrap();
+    assert_eq!((size.value.as_str(), size.config, size.section.as_str()), ("4", 1, "*.rs"));
+    assert!(matrix.rows[1].get("indent_size").is_none());
+    assert_eq!(
+        matrix.to_markdown(),
+        "| Extension | `indent_style` | `indent_size` |
+|---|---|---|
+| `.rs` | `space` from `[*]` | `4` from `[*.rs]` |
+| `.md` | `space` from `[*]` | |
+"
+    );
+    assert!(matrix.to_json().starts_with(
+        r#"[{"extension":"rs","properties":{"indent_style":{"value":"space","config":0,"section":"*"},"#
+    ));
+}
+
+#[test]
+fn parse_with_spans() {
+    let source = "root = true\n\n[*.rs]\n  indent_size = 4\nfoo=bar\n";
+    let (editor_config, spans) = EditorConfig::parse_with_spans(source);
+    assert_eq!(editor_config.sections().len(), 1);
+    let span = |start, end| Span { start, end };
+    assert_eq!(
+        spans,
+        [SectionSpans {
+            header: span(13, 19),
+            name: span(14, 18),
+            pairs: vec![
+                PairSpans { key: span(22, 33), value: span(36, 37) },
+                PairSpans { key: span(38, 41), value: span(42, 45) },
+            ],
+        }]
+    );
+    assert_eq!(&source[spans[0].pairs[0].key.start..spans[0].pairs[0].key.end], "indent_size");
+}
+
+#[test]
+fn document_round_trip() {
+    let source = "# top\r\nroot = true\r\n\r\n[*]\r\n  indent_size =  2 ; not a comment\r\n; note\r\n\r\n[*.md]\r\ncustom=1";
+    let mut document = EditorConfigDocument::parse(source);
+    assert_eq!(document.to_string(), source);
+    assert_eq!(document.get("*", "indent_size"), Some("2 ; not a comment"));
+
+    document.set("*", "indent_size", "4");
+    document.set("*", "indent_style", "space");
+    document.set("*.md", "max_line_length", "off");
+    document.set("Makefile", "indent_style", "tab");
+    assert!(document.remove("*.md", "custom"));
+    assert!(!document.remove("*.md", "custom"));
+    assert_eq!(
+        document.to_string(),
+        "# top\r\nroot = true\r\n\r\n[*]\r\n  indent_size =  4\r\nindent_style = space\n; note\r\n\r\n[*.md]\r\nmax_line_length = off\n\n[Makefile]\nindent_style = tab\n"
+    );
+    let config = document.to_config();
+    assert_eq!(config.sections().len(), 3);
+    assert_eq!(
+        config.resolve(Path::new("a.rs")).indent_size,
+        EditorConfigProperty::Value(IndentSize::Number(4))
+    );
+}
+
+#[test]
+fn resolve_property() {
+    let source = "[*]\nend_of_line = lf\nindent_size = 2\n[*.rs]\nindent_size = 4\n[*.md]\nend_of_line = unset\n[*.{md,txt}]\nindent_size = tab\n[*.txt]\ntab_width = 8\n[Makefile]\nindent_style = tab\nindent_size = unset";
+    for spec_version in [None, Some(SpecVersion::V0_10_0)] {
+        let options = ParseOptions { spec_version, ..ParseOptions::default() };
+        let (editor_config, _) = EditorConfig::parse_with_options(source, &options);
+        for path in ["a.rs", "a.md", "a.txt", "Makefile"] {
+            let path = Path::new(path);
+            let resolved = editor_config.resolve(path);
+            for key in PropertyKey::ALL {
+                let property = editor_config.resolve_property(path, key);
+                assert_eq!(property, resolved.get(key), "{path:?} {key}");
+            }
+        }
+    }
+    let editor_config = EditorConfig::parse(source);
+    assert_eq!(
+        editor_config.resolve_property(Path::new("a.rs"), PropertyKey::IndentSize),
+        Value(PropertyValue::IndentSize(IndentSize::Number(4)))
+    );
+}
+
+#[test]
+fn unknown_properties() {
+    let editor_config = EditorConfig::parse(
+        "[*.java]\nij_java_imports_layout = *,|\nindent_size = 4\nquote_type = single",
+    );
+    let section = &editor_config.sections()[0];
+    assert_eq!(
+        section.unknown_properties,
+        [
+            ("ij_java_imports_layout".to_string(), "*,|".to_string()),
+            ("quote_type".to_string(), "single".to_string()),
+        ]
+    );
+    assert_eq!(section.properties.indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(
+        editor_config.to_string(),
+        "[*.java]\nindent_size = 4\nij_java_imports_layout = *,|\nquote_type = single\n"
+    );
+}
+
+#[test]
+fn detect_charset() {
+    assert_eq!(detect::charset(b"\xEF\xBB\xBFfn main() {}"), Some(Charset::Utf8bom));
+    assert_eq!(detect::charset(b"\xFE\xFF\x00a"), Some(Charset::Utf16be));
+    assert_eq!(detect::charset(b"\xFF\xFEa\x00"), Some(Charset::Utf16le));
+    assert_eq!(detect::charset(b""), Some(Charset::Utf8));
+    assert_eq!(detect::charset("caf\u{e9}".as_bytes()), Some(Charset::Utf8));
+    assert_eq!(detect::charset(b"caf\xE9"), Some(Charset::Latin1));
+    assert_eq!(detect::charset(b"\x93quoted\x94"), None);
+    assert_eq!(detect::charset(b"a\x00b\x00"), None);
+}
+
+#[test]
+fn preamble() {
+    let editor_config =
+        EditorConfig::parse("# tool settings\nroot = true\nmy_tool_version = 2\n[*]\nfoo = bar");
+    assert!(editor_config.root());
+    assert_eq!(editor_config.preamble(), [("my_tool_version".to_string(), "2".to_string())]);
+    assert_eq!(editor_config.to_string(), "root = true\nmy_tool_version = 2\n\n[*]\nfoo = bar\n");
+}
+
+#[test]
+fn case_insensitive_keys() {
+    let editor_config = EditorConfig::parse(
+        "ROOT = true\n[*]\nINDENT_STYLE = tab\nIndent_Size = 4\nEnd_Of_Line = lf\nQuote_Type = single",
+    );
+    assert!(editor_config.root());
+    let properties = editor_config.resolve(Path::new("a.rs"));
+    assert_eq!(properties.indent_style, Value(IndentStyle::Tab));
+    assert_eq!(properties.indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(properties.end_of_line, Value(EndOfLine::Lf));
+    assert_eq!(
+        editor_config.sections()[0].unknown_properties,
+        [("quote_type".to_string(), "single".to_string())]
+    );
+    assert!(EditorConfig::builder().section("*").unwrap().property("TAB_WIDTH", "x").is_err());
+}
+
+#[test]
+fn normalize_values() {
+    assert_eq!(value::normalize("Indent_Style", "TAB"), "tab");
+    assert_eq!(value::normalize("max_line_length", "Off"), "off");
+    assert_eq!(value::normalize("quote_type", "Single"), "Single");
+
+    let editor_config = EditorConfig::parse("[*]\nIndent_Style = TAB\nQuote_Type = Single");
+    assert_eq!(editor_config.to_string(), "[*]\nindent_style = tab\nquote_type = Single\n");
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn config_store() {
+    use editorconfig_parser::store::ConfigStore;
+
+    let store = ConfigStore::new();
+    assert_eq!(store.replace("/repo", EditorConfig::parse("[*]\nindent_size = 2")), 1);
+    let before = store.snapshot();
+    std::thread::scope(|scope| {
+        scope.spawn(|| store.replace("/repo", EditorConfig::parse("[*]\nindent_size = 4")));
+        scope.spawn(|| store.replace("/repo/docs", EditorConfig::parse("[*.md]\ncharset = utf-8")));
+    });
+    assert_eq!(store.version(), 3);
+
+    // Snapshots are unaffected by later changes.
+    let resolve = |config: &EditorConfig| config.resolve(Path::new("a.rs")).indent_size;
+    assert_eq!(resolve(before.get(Path::new("/repo")).unwrap()), Value(IndentSize::Number(2)));
+    let after = store.snapshot();
+    assert_eq!(resolve(after.get(Path::new("/repo")).unwrap()), Value(IndentSize::Number(4)));
+    assert_eq!(after.dirs().count(), 2);
+
+    assert_eq!(store.remove(Path::new("/repo/docs")), 4);
+    assert!(store.snapshot().get(Path::new("/repo/docs")).is_none());
+}
+
+#[test]
+fn resolve_shared_match_sets() {
+    let editor_config = EditorConfig::parse(
+        "[*]\nindent_size = 2\n[*.ts]\nindent_size = 4\n[*.TS]\nindent_size = 8",
+    );
+    for path in ["a.ts", "src/b.ts", "a.ts"] {
+        let resolved = editor_config.resolve_with_matches(Path::new(path));
+        assert_eq!(
+            (resolved.properties.indent_size, resolved.matched_sections),
+            (Value(IndentSize::Number(4)), 2)
+        );
+    }
+    // Clones share merged properties, which stay valid when matching changes.
+    let insensitive = editor_config.clone().with_case_insensitive(true);
+    assert_eq!(insensitive.resolve(Path::new("a.ts")).indent_size, Value(IndentSize::Number(8)));
+    assert_eq!(editor_config.resolve(Path::new("a.ts")).indent_size, Value(IndentSize::Number(4)));
+}
+
+#[test]
+fn utf8_bom() {
+    let source = "\u{feff}root = true\n[*]\nindent_size = 2";
+    let editor_config = EditorConfig::parse(source);
+    assert!(editor_config.root());
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(2)));
+    // Spans are offsets into the text as given, including the BOM.
+    let (_, spans) = EditorConfig::parse_with_spans(source);
+    assert_eq!(&source[spans[0].header.start..spans[0].header.end], "[*]");
+}
+
+#[test]
+fn parse_bytes() {
+    let text = "root = true\n[caf\u{e9}.txt]\nindent_size = 2\n";
+    let utf16le = [0xFF, 0xFE]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect::<Vec<_>>();
+    let utf16be = [0xFE, 0xFF]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect::<Vec<_>>();
+    let latin1 = text.chars().map(|c| u8::try_from(c).unwrap()).collect::<Vec<_>>();
+    let utf8bom = [b"\xEF\xBB\xBF", text.as_bytes()].concat();
+    for bytes in [text.as_bytes(), &utf8bom, &utf16le, &utf16be, &latin1] {
+        let editor_config = EditorConfig::parse_bytes(bytes);
+        assert!(editor_config.root());
+        assert_eq!(editor_config.sections()[0].name, "caf\u{e9}.txt");
+        assert_eq!(
+            editor_config.resolve(Path::new("caf\u{e9}.txt")).indent_size,
+            Value(IndentSize::Number(2))
+        );
+    }
+}
+
+#[test]
+fn document_transaction() {
+    let document = EditorConfigDocument::parse("# settings\n[*]\nindent_size = 2\n");
+    let edited = document
+        .transaction()
+        .set("*", "indent_size", "4")
+        .set("*.md", "max_line_length", "off")
+        .remove("*", "missing")
+        .commit()
+        .unwrap();
+    assert_eq!(
+        edited.to_string(),
+        "# settings\n[*]\nindent_size = 4\n\n[*.md]\nmax_line_length = off\n"
+    );
+
+    let violations = document
+        .transaction()
+        .set("*", "indent_size", "four")
+        .set("build/", "charset", "utf-8")
+        .commit()
+        .unwrap_err();
+    assert!(matches!(&violations[..], [EditViolation::Parse(err)] if err.text == "four"));
+    let violations = document.transaction().set("build/", "charset", "utf-8").commit().unwrap_err();
+    assert!(matches!(
+        &violations[..],
+        [EditViolation::GitignoreIsm { section, .. }] if section == "build/"
+    ));
+    assert_eq!(document.to_string(), "# settings\n[*]\nindent_size = 2\n");
+
+    // Untouched problems elsewhere don't block an edit.
+    let document =
+        EditorConfigDocument::parse("[build/]\ncharset = bogus\nindent_size = two\n[*]\n");
+    let edited = document.transaction().set("*", "indent_size", "4").commit().unwrap();
+    assert_eq!(edited.get("*", "indent_size"), Some("4"));
+    let violations =
+        document.transaction().set("*", "indent_size", "4\n[*.md]").commit().unwrap_err();
+    assert_eq!(
+        violations,
+        [EditViolation::LineBreak { section: "*".to_string(), key: "indent_size".to_string() }]
+    );
+}
+
+#[test]
+fn from_reader() {
+    let source =
+        "\u{feff}root = true\r\n[*.rs]\r\nindent_size = 4\r\n[*.md]\nmax_line_length = off";
+    let editor_config = EditorConfig::from_reader(std::io::Cursor::new(source)).unwrap();
+    assert_eq!(editor_config.to_string(), EditorConfig::parse(source).to_string());
+    assert!(editor_config.root());
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(4)));
+
+    let invalid = std::io::Cursor::new(b"[*]\nindent_size = \xFF\n");
+    assert_eq!(
+        EditorConfig::from_reader(invalid).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn core_length_limits() {
+    let long_key = "k".repeat(1025);
+    // 4096 characters, but 8192 bytes.
+    let long_value = "\u{e9}".repeat(4096);
+    let source = format!("[*]\n{long_key} = 1\nquote_type = {long_value}\nindent_size = 2");
+    let (editor_config, _) =
+        EditorConfig::parse_with_options(&source, &ParseOptions::core_compat());
+    let section = &editor_config.sections()[0];
+    assert!(section.unknown_properties.is_empty());
+    assert_eq!(section.properties.indent_size, Value(IndentSize::Number(2)));
+
+    if !cfg!(feature = "spec-strict") {
+        let editor_config = EditorConfig::parse(&source);
+        assert_eq!(editor_config.sections()[0].unknown_properties.len(), 2);
+    }
+}
+
+#[test]
+fn line_records() {
+    let source = "root = true\n\n[*]\n# comment\n  indent_size = 2\n[broken\n";
+    let editor_config = EditorConfig::parse(source);
+    let lines = editor_config.lines();
+    assert_eq!(
+        lines.iter().map(|line| (line.kind, line.section)).collect::<Vec<_>>(),
+        [
+            (LineKind::Pair, None),
+            (LineKind::Blank, None),
+            (LineKind::Header, Some(0)),
+            (LineKind::Comment, Some(0)),
+            (LineKind::Pair, Some(0)),
+            (LineKind::Other, Some(0)),
+        ]
+    );
+    let text = |span: Span| &source[span.start..span.end];
+    let LineRecord { span, key, value, .. } = lines[4];
+    assert_eq!(
+        (text(span), text(key.unwrap()), text(value.unwrap())),
+        ("indent_size = 2", "indent_size", "2")
+    );
+}
+
+#[test]
+fn parse_warnings() {
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(
+        "indent_style = tab\nmy_tool = 1\n[*]\nindent_size = two\nquote_type = anything",
+    );
+    assert_eq!(editor_config.sections().len(), 1);
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic {
+                line: 1,
+                kind: DiagnosticKind::NoSection { key: "indent_style".to_string() }
+            },
+            Diagnostic {
+                line: 4,
+                kind: DiagnosticKind::InvalidValue {
+                    key: "indent_size".to_string(),
+                    value: "two".to_string()
+                }
+            },
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "line 1: property `indent_style` must be inside a section"
+    );
+    assert_eq!(diagnostics[1].code(), Some("EC002"));
+}
+
+#[test]
+fn resolve_observer() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Events(Mutex<Vec<(String, usize, bool)>>);
+
+    impl ResolveObserver for Events {
+        fn resolved(&self, event: &ResolveEvent<'_>) {
+            let path = String::from_utf8_lossy(event.path).into_owned();
+            self.0.lock().unwrap().push((path, event.matched_sections, event.cache_hit));
+        }
+    }
+
+    let events = Arc::new(Events::default());
+    let editor_config = EditorConfig::parse("[*]\nindent_size = 2\n[*.rs]\nindent_size = 4")
+        .with_resolve_observer(Arc::clone(&events) as Arc<dyn ResolveObserver>);
+    editor_config.resolve(Path::new("a.rs"));
+    editor_config.resolve(Path::new("b.rs"));
+    editor_config.resolve(Path::new("c.md"));
+    assert_eq!(
+        *events.0.lock().unwrap(),
+        [
+            ("a.rs".to_string(), 2, false),
+            ("b.rs".to_string(), 2, true),
+            ("c.md".to_string(), 1, false),
+        ]
+    );
+
+    // Every resolve entry point notifies the observer and shares the merge cache.
+    events.0.lock().unwrap().clear();
+    editor_config.resolve_filename("d.rs");
+    let options = ResolveOptions { outside_cwd: OutsideCwd::SkipAnchoredSections };
+    editor_config
+        .clone()
+        .with_cwd("/project")
+        .resolve_with_options(Path::new("/e.md"), &options)
+        .unwrap();
+    #[cfg(feature = "unstable")]
+    editor_config.for_directory(Path::new("src")).resolve(Path::new("src/f.rs"));
+    let mut expected = vec![("d.rs".to_string(), 2, true), ("/e.md".to_string(), 1, true)];
+    if cfg!(feature = "unstable") {
+        expected.push(("src/f.rs".to_string(), 2, true));
+    }
+    assert_eq!(*events.0.lock().unwrap(), expected);
+}
+
+#[test]
+fn property_key_from_str() {
+    for key in PropertyKey::ALL {
+        assert_eq!(key.as_str().parse(), Ok(key));
+        assert_eq!(key.to_string(), key.as_str());
+    }
+    assert_eq!("Charset".parse(), Ok(PropertyKey::Charset));
+    let error = "quote_type".parse::<PropertyKey>().unwrap_err();
+    assert_eq!(error, UnknownPropertyKey { key: "quote_type".to_string() });
+    assert_eq!(error.to_string(), "unknown property `quote_type`");
+}
+
+#[test]
+fn iterate_properties() {
+    let editor_config = EditorConfig::parse(
+        "[*]\ncharset = UTF-8\nindent_size = tab\ntab_width = unset\nquote_type = single",
+    );
+    let pairs: Vec<_> = editor_config.sections()[0].properties.iter().collect();
+    assert_eq!(
+        pairs,
+        [
+            (PropertyKey::IndentSize, "tab".to_string()),
+            (PropertyKey::TabWidth, "unset".to_string()),
+            (PropertyKey::Charset, "utf-8".to_string()),
+        ]
+    );
+    let properties = editor_config.resolve(Path::new("a.rs"));
+    let lines: Vec<String> =
+        (&properties).into_iter().map(|(key, value)| format!("{key}={value}")).collect();
+    assert_eq!(lines, ["indent_size=tab", "charset=utf-8"]);
+}
+
+#[test]
+fn property_completions() {
+    for key in PropertyKey::ALL {
+        let keywords = match key.syntax() {
+            ValueSyntax::Keywords(keywords) => keywords,
+            ValueSyntax::Number { keywords } => keywords,
+        };
+        for value in keywords.iter().chain(&["unset"]) {
+            let (_, diagnostics) =
+                EditorConfig::parse_with_diagnostics(&format!("[*]\n{} = {value}", key.name()));
+            assert_eq!(diagnostics, [], "{} = {value}", key.name());
+        }
+    }
+    let completions = completions();
+    assert_eq!(completions.lines().count(), PropertyKey::ALL.len());
+    assert!(
+        completions.starts_with("indent_style\ttab space unset\nindent_size\t<number> tab unset\n")
+    );
+    assert!(completions.contains("max_line_length\t<number> off unset\n"));
+}
+
+#[test]
+fn malformed_section_header() {
+    let source = "[*]\nindent_size = 2\n[*.py\nindent_size = 4";
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(source);
+    assert_eq!(editor_config.resolve(Path::new("a.py")).indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic {
+                line: 3,
+                kind: DiagnosticKind::InvalidHeader { header: "[*.py".to_string() }
+            },
+            // The pair still belongs to `[*]`.
+            Diagnostic {
+                line: 4,
+                kind: DiagnosticKind::DuplicateKey {
+                    key: "indent_size".to_string(),
+                    previous_line: 2
+                }
+            },
+        ]
+    );
+    assert_eq!(diagnostics[0].code(), Some("EC006"));
+
+    let errors = EditorConfig::try_parse(source).unwrap_err();
+    assert!(matches!(
+        &errors[..],
+        [ParseError { line: 3, kind: ParseErrorKind::InvalidHeader, .. }]
+    ));
+}
+
+#[test]
+fn resolve_filename() {
+    let editor_config = EditorConfig::parse(
+        "[*]\nindent_size = 2\n[*.rs]\nindent_size = 4\n[src/*.rs]\nindent_size = 8\n[Makefile]\nindent_style = tab",
+    )
+    .with_cwd("/repo");
+    assert_eq!(editor_config.resolve_filename("main.rs").indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(editor_config.resolve_filename("Makefile").indent_style, Value(IndentStyle::Tab));
+    assert_eq!(editor_config.resolve_filename("README").indent_size, Value(IndentSize::Number(2)));
+}
+
+#[test]
+fn text_after_section_header() {
+    // From editorconfig-core-test `parser/comments_after_section.in` and
+    // `parser/bracket_in_section.in`.
+    let source = "[test.c] ; comment\nkey = value\n[a]b]\nindent_size = 2\n[*.md]x\n[broken\n";
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(source);
+    let names = editor_config.sections().iter().map(|s| s.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["test.c", "a]b", "*.md"]);
+    assert_eq!(editor_config.sections()[0].unknown_properties.len(), 1);
+    assert_eq!(editor_config.resolve(Path::new("a]b")).indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.line).collect::<Vec<_>>(), [6]);
+
+    let (_, spans) = EditorConfig::parse_with_spans(source);
+    assert_eq!(&source[spans[0].header.start..spans[0].header.end], "[test.c]");
+    assert_eq!(&source[spans[1].name.start..spans[1].name.end], "a]b");
+
+    let mut document = EditorConfigDocument::parse(source);
+    document.set("test.c", "indent_size", "4");
+    assert!(document.to_string().starts_with("[test.c] ; comment\nkey = value\nindent_size = 4\n"));
+}
+
+#[test]
+fn duplicate_sections() {
+    let source =
+        "[*.md]\nindent_size = 2\ntab_width = 8\n[*]\nindent_size = 4\n[*.md]\ntab_width = 3\n";
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(source);
+    let duplicate = DiagnosticKind::DuplicateSection { name: "*.md".to_string(), first_line: 1 };
+    assert_eq!(diagnostics, [Diagnostic { line: 6, kind: duplicate.clone() }]);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "line 6: section `[*.md]` repeats the section on line 1"
+    );
+    assert_eq!(diagnostics[0].code(), Some("EC007"));
+    assert_eq!(editor_config.sections().len(), 3);
+    let properties = editor_config.resolve(Path::new("a.md"));
+    assert_eq!(
+        (properties.indent_size, properties.tab_width),
+        (Value(IndentSize::Number(4)), Value(3))
+    );
+
+    let options = ParseOptions { merge_duplicate_sections: true, ..ParseOptions::default() };
+    let (editor_config, diagnostics) = EditorConfig::parse_with_options(source, &options);
+    // Merging makes the second `tab_width` a duplicate key.
+    let duplicate_key =
+        DiagnosticKind::DuplicateKey { key: "tab_width".to_string(), previous_line: 3 };
+    assert_eq!(
+        diagnostics,
+        [Diagnostic { line: 6, kind: duplicate }, Diagnostic { line: 7, kind: duplicate_key }]
+    );
+    assert_eq!(editor_config.sections().len(), 2);
+    assert_eq!(editor_config.sections()[0].properties.tab_width, Value(3));
+    assert_eq!(editor_config.lines()[6].section, Some(0));
+    let properties = editor_config.resolve(Path::new("a.md"));
+    assert_eq!(
+        (properties.indent_size, properties.tab_width),
+        (Value(IndentSize::Number(4)), Value(3))
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let source = "[*]\nindent_size = 2\nINDENT_SIZE = 4\nquote_type = single\n[*.md]\nindent_size = 8\nindent_size = 3\nquote_type = double\n";
+    let (editor_config, diagnostics) = EditorConfig::parse_with_diagnostics(source);
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic {
+                line: 3,
+                kind: DiagnosticKind::DuplicateKey {
+                    key: "indent_size".to_string(),
+                    previous_line: 2
+                }
+            },
+            Diagnostic {
+                line: 7,
+                kind: DiagnosticKind::DuplicateKey {
+                    key: "indent_size".to_string(),
+                    previous_line: 6
+                }
+            },
+        ]
+    );
+    assert_eq!(diagnostics[0].to_string(), "line 3: `indent_size` overrides the value on line 2");
+    assert_eq!(diagnostics[0].code(), Some("EC008"));
+    assert_eq!(editor_config.sections()[0].properties.indent_size, Value(IndentSize::Number(4)));
+}
+
+#[test]
+fn semantics() {
+    let editor_config = EditorConfig::parse("[*]\nindent_size = 2");
+    let semantics = editor_config.semantics();
+    assert_eq!(semantics.version, semantics_version());
+    assert_eq!(semantics, EditorConfig::parse("[*.md]").semantics());
+    let json = semantics.to_json();
+    assert!(json.starts_with(&format!("{{\"version\":{},\"glob_backend\":", semantics_version())));
+    let GlobLimits { max_pattern_len, max_expansions } = semantics.glob_limits;
+    assert!(json.contains(&format!(
+        "\"glob_limits\":{{\"max_pattern_len\":{max_pattern_len},\"max_expansions\":{max_expansions}}},\"spec_version\":null,"
+    )));
+    assert!(json.ends_with(",\"max_line_length_off_aliases\":[]}"));
+
+    let options = ParseOptions {
+        inline_comments: true,
+        max_line_length_off_aliases: vec!["none".to_string()],
+        ..ParseOptions::default()
+    };
+    let with_options = EditorConfig::parse_with_options("[*]", &options).0.semantics();
+    assert_ne!(with_options, semantics);
+    let json = with_options.to_json();
+    assert!(json.contains("\"inline_comments\":true"));
+    assert!(json.ends_with(",\"max_line_length_off_aliases\":[\"none\"]}"));
+
+    let case_insensitive = editor_config.with_case_insensitive(true).semantics();
+    assert_ne!(case_insensitive, semantics);
+    assert!(case_insensitive.to_json().contains("\"case_insensitive\":true"));
+}
+
+#[test]
+fn resolve_all() {
+    let editor_config = EditorConfig::parse("[*.rs]\nindent_size = 4\n[*.md]\nindent_size = 2");
+    let cancel = AtomicBool::new(false);
+    let resolved = editor_config.resolve_all(["a.rs", "b.md"], &cancel).unwrap();
+    assert_eq!(
+        resolved.iter().map(|properties| properties.indent_size.clone()).collect::<Vec<_>>(),
+        [Value(IndentSize::Number(4)), Value(IndentSize::Number(2))]
+    );
+
+    cancel.store(true, Ordering::Relaxed);
+    assert_eq!(editor_config.resolve_all(["a.rs"], &cancel), Err(Cancelled));
+    assert_eq!(editor_config.resolve_all(Vec::<&Path>::new(), &cancel), Ok(vec![]));
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn reduce() {
+    use editorconfig_parser::reduce::reduce;
+
+    let source = "root = true\n\n[*]\nindent_style = space\nindent_size = 2\n\n[*.md]\ntrim_trailing_whitespace = false\nindent_size = 4\n\n[[z-a]]\ncharset = utf-8\n";
+    // A "bug" needing `*.md` to resolve `indent_size = 4`.
+    let mut calls = 0;
+    let reduced = reduce(source, |candidate| {
+        calls += 1;
+        EditorConfig::parse(candidate).resolve(Path::new("a.md")).indent_size
+            == Value(IndentSize::Number(4))
+    });
+    assert_eq!(reduced.as_deref(), Some("[*.md]\nindent_size = 4\n"));
+    let mut checks = 0;
+    let unreproduced = reduce(source, |candidate| {
+        checks += 1;
+        candidate.is_empty()
+    });
+    assert_eq!((unreproduced, checks), (None, 1));
+
+    let mut again = 0;
+    reduce(source, |candidate| {
+        again += 1;
+        EditorConfig::parse(candidate).resolve(Path::new("a.md")).indent_size
+            == Value(IndentSize::Number(4))
+    });
+    assert_eq!(calls, again);
+}
+
+#[test]
+fn line_endings() {
+    let source = "root = true\r[*]\rindent_size = 2\r\n[*.md]\nindent_size = 4\r\r\n";
+    let editor_config = EditorConfig::parse(source);
+    assert!(editor_config.root());
+    assert_eq!(editor_config.sections().len(), 2);
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(editor_config.resolve(Path::new("a.md")).indent_size, Value(IndentSize::Number(4)));
+    assert_eq!(editor_config.lines().len(), 6);
+
+    let from_reader = EditorConfig::from_reader(std::io::Cursor::new(source)).unwrap();
+    assert_eq!(from_reader.to_string(), editor_config.to_string());
+    assert_eq!(from_reader.lines(), editor_config.lines());
+
+    let mut document = EditorConfigDocument::parse(source);
+    assert_eq!(document.to_string(), source);
+    document.set("*", "indent_size", "3");
+    assert_eq!(document.to_string(), source.replace("= 2", "= 3"));
+}
+
+#[test]
+fn spec_version() {
+    let source = "[*]\nindent_style = tab\ntab_width = 8\n[*.md]\nindent_size = 2";
+    let resolve = |spec_version, path| {
+        let options = ParseOptions { spec_version, ..ParseOptions::default() };
+        let properties =
+            EditorConfig::parse_with_options(source, &options).0.resolve(Path::new(path));
+        (properties.indent_size, properties.tab_width)
+    };
+    assert_eq!(resolve(None, "a.rs"), (EditorConfigProperty::None, Value(8)));
+    assert_eq!(
+        resolve(Some(SpecVersion::new(0, 9, 0)), "a.rs"),
+        (EditorConfigProperty::None, Value(8))
+    );
+    assert_eq!(
+        resolve(Some(SpecVersion::V0_10_0), "a.rs"),
+        (Value(IndentSize::Number(8)), Value(8))
+    );
+    assert_eq!(
+        resolve(Some(SpecVersion::new(0, 9, 0)), "a.md"),
+        (Value(IndentSize::Number(2)), Value(8))
+    );
+
+    let source = "[*]\nindent_size = 2";
+    let options =
+        ParseOptions { spec_version: Some(SpecVersion::new(0, 17, 2)), ..ParseOptions::default() };
+    let editor_config = EditorConfig::parse_with_options(source, &options).0;
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).tab_width, Value(2));
+    assert_eq!(editor_config.resolve_filename("a.rs").tab_width, Value(2));
+    assert!(editor_config.semantics().to_json().contains(",\"spec_version\":\"0.17.2\","));
+    assert_eq!(
+        EditorConfig::parse(source).resolve(Path::new("a.rs")).tab_width,
+        EditorConfigProperty::None
+    );
+}
+
+#[test]
+fn max_line_length_off_aliases() {
+    let source = "[*.md]\nmax_line_length = None\n[*.py]\nmax_line_length = 0\n[*.rs]\nmax_line_length = 100";
+    let options = ParseOptions {
+        max_line_length_off_aliases: vec!["none".to_string(), "0".to_string()],
+        ..ParseOptions::default()
+    };
+    let (editor_config, diagnostics) = EditorConfig::parse_with_options(source, &options);
+    if cfg!(feature = "spec-strict") {
+        assert_eq!(diagnostics.len(), 1);
+        let properties = &editor_config.sections()[1].properties;
+        assert_eq!(properties.max_line_length, Value(MaxLineLength::Number(0)));
+        return;
+    }
+    assert!(diagnostics.is_empty());
+    let max_line_length = |i: usize| editor_config.sections()[i].properties.max_line_length.clone();
+    assert_eq!(max_line_length(0), Value(MaxLineLength::Off));
+    assert_eq!(max_line_length(1), Value(MaxLineLength::Off));
+    assert_eq!(max_line_length(2), Value(MaxLineLength::Number(100)));
+    assert_eq!(editor_config.sections()[0].raw_value(PropertyKey::MaxLineLength), Some("None"));
+
+    let editor_config = EditorConfig::parse(source);
+    assert_eq!(editor_config.sections()[0].properties.max_line_length, EditorConfigProperty::None);
+    assert_eq!(
+        editor_config.sections()[1].properties.max_line_length,
+        Value(MaxLineLength::Number(0))
+    );
+}
+
+#[cfg(not(feature = "spec-strict"))]
+#[test]
+fn inline_comments() {
+    let source = "root = true # top\n[*]\nindent_size = 2 ; two\nend_of_line = lf#not a comment\nquote_type = ;\n";
+    let options = ParseOptions { inline_comments: true, ..ParseOptions::default() };
+    let (editor_config, diagnostics) = EditorConfig::parse_with_options(source, &options);
+    assert!(editor_config.root());
+    let section = &editor_config.sections()[0];
+    assert_eq!(section.properties.indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(section.properties.end_of_line, EditorConfigProperty::None);
+    assert_eq!(section.unknown_properties, [("quote_type".to_string(), String::new())]);
+    assert_eq!(diagnostics.len(), 1);
+    let value = editor_config.lines()[2].value.unwrap();
+    assert_eq!(&source[value.start..value.end], "2");
+
+    let editor_config = EditorConfig::parse(source);
+    assert!(!editor_config.root());
+    assert_eq!(editor_config.sections()[0].properties.indent_size, EditorConfigProperty::None);
+}
+
+#[test]
+fn document_comments() {
+    let source = "# defaults\n[*]\n# 4-wide because of legacy code\n; really\nindent_size = 4\n\n# stray\n\nindent_style = space\n# trailing";
+    let mut document = EditorConfigDocument::parse(source);
+    assert_eq!(document.comments("*", None), ["# defaults"]);
+    assert_eq!(
+        document.comments("*", Some("INDENT_SIZE")),
+        ["# 4-wide because of legacy code", "; really"]
+    );
+    assert!(document.comments("*", Some("indent_style")).is_empty());
+    assert!(document.comments("*.md", None).is_empty());
+
+    assert!(document.move_pair("*", "indent_size", None));
+    assert_eq!(
+        document.to_string(),
+        "# defaults\n[*]\n\n# stray\n\nindent_style = space\n# 4-wide because of legacy code\n; really\nindent_size = 4\n# trailing"
+    );
+    assert!(document.move_pair("*", "indent_size", Some("indent_style")));
+    assert!(!document.move_pair("*", "indent_size", Some("missing")));
+    assert_eq!(
+        document.comments("*", Some("indent_size")),
+        ["# 4-wide because of legacy code", "; really"]
+    );
+
+    assert!(document.remove("*", "indent_size"));
+    assert_eq!(
+        document.to_string(),
+        "# defaults\n[*]\n\n# stray\n\nindent_style = space\n# trailing"
+    );
+}
+
+#[test]
+fn source_ranges() {
+    let source = "root = true\n[*] ; all\nindent_size = 2\n  quote_type = single  \n\n# md\n[*.md]\n[*.rs]\ntab_width = 4\n";
+    let (editor_config, spans) = EditorConfig::parse_with_spans(source);
+    let slice = |range: std::ops::Range<usize>| &source[range];
+    assert_eq!(
+        slice(editor_config.section_source_range(0).unwrap()),
+        "[*] ; all\nindent_size = 2\n  quote_type = single"
+    );
+    assert_eq!(slice(editor_config.section_source_range(1).unwrap()), "[*.md]");
+    assert_eq!(slice(editor_config.section_source_range(2).unwrap()), "[*.rs]\ntab_width = 4");
+    assert_eq!(editor_config.section_source_range(3), None);
+
+    assert_eq!(slice(spans[0].source_range()), "[*] ; all\nindent_size = 2\n  quote_type = single");
+    for (index, spans) in spans.iter().enumerate() {
+        assert_eq!(editor_config.section_source_range(index), Some(spans.source_range()));
+    }
+
+    assert_eq!(slice(spans[0].pairs[1].source_range()), "quote_type = single");
+    assert_eq!(slice(spans[2].pairs[0].value.range()), "4");
+
+    let mut spliced = source.to_string();
+    spliced.replace_range(spans[2].pairs[0].source_range(), "tab_width = 8");
+    assert_eq!(EditorConfig::parse(&spliced).resolve(Path::new("a.rs")).tab_width, Value(8));
+
+    // Text after the `]` of a header without pairs is not part of the section.
+    let source = "[*.rs] ; comment\n[*.md]\n";
+    let (editor_config, spans) = EditorConfig::parse_with_spans(source);
+    assert_eq!(editor_config.section_source_range(0), Some(spans[0].source_range()));
+    assert_eq!(&source[spans[0].source_range()], "[*.rs]");
+}
+
+#[test]
+fn input_limits() {
+    let parse = |source: &str, input_limits| {
+        let options = ParseOptions { input_limits, ..ParseOptions::default() };
+        EditorConfig::parse_with_options(source, &options)
+    };
+    let source = "[*]\nindent_size = 2\n[*.md]\nindent_size = 4\n[*.rs]\nindent_size = 8\n";
+    let limits = InputLimits { max_input_len: 30, ..InputLimits::default() };
+    let (editor_config, diagnostics) = parse(source, limits);
+    assert_eq!(editor_config.sections().len(), 2);
+    assert_eq!(editor_config.sections()[1].properties.indent_size, EditorConfigProperty::None);
+    assert_eq!(
+        diagnostics,
+        [Diagnostic { line: 4, kind: DiagnosticKind::InputTooLong { max: 30 } }]
+    );
+    assert_eq!(diagnostics[0].code(), Some("EC009"));
+
+    let limits = InputLimits { max_sections: 2, ..InputLimits::default() };
+    let (editor_config, diagnostics) = parse(source, limits);
+    assert_eq!(editor_config.sections().len(), 2);
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(diagnostics[0].to_string(), "line 5: more than 2 sections, the rest is ignored");
+    assert_eq!(editor_config.lines().len(), 4);
+
+    let source = format!("[*]\nquote_type = {}\nindent_size = 2", "x".repeat(100));
+    let (editor_config, diagnostics) =
+        parse(&source, InputLimits { max_line_len: 64, ..InputLimits::default() });
+    assert!(editor_config.sections()[0].unknown_properties.is_empty());
+    assert_eq!(editor_config.sections()[0].properties.indent_size, Value(IndentSize::Number(2)));
+    assert_eq!(
+        diagnostics,
+        [Diagnostic { line: 2, kind: DiagnosticKind::LineTooLong { max: 64 } }]
+    );
+
+    let (_, diagnostics) = parse(&source, InputLimits::untrusted());
+    assert!(diagnostics.is_empty());
+
+    // Repeated headers start new sections, so they count towards the limit.
+    let limits = InputLimits { max_sections: 2, ..InputLimits::default() };
+    let (editor_config, _) = parse(
+        "[a]
+[a]
+[a]
+[a]
+[a]
+",
+        limits,
+    );
+    assert_eq!(editor_config.sections().len(), 2);
+
+    // Pairs under an over-long header do not apply to the previous section.
+    let source = format!(
+        "[*.rs]
+indent_size = 2
+[{}.rs]
+indent_size = 8",
+        "x".repeat(100)
+    );
+    let (editor_config, _) =
+        parse(&source, InputLimits { max_line_len: 64, ..InputLimits::default() });
+    assert_eq!(editor_config.resolve(Path::new("a.rs")).indent_size, Value(IndentSize::Number(2)));
+}
+
+#[test]
+fn resolve_with_spec_defaults() {
+    let editor_config = EditorConfig::parse(
+        "[*]\nindent_size = tab\ntab_width = 8\n[*.md]\nindent_size = 2\ntab_width = unset\n[*.txt]\nindent_size = TAB\n",
+    );
+    let (section, diagnostics) = EditorConfig::parse_with_diagnostics("[*]\nindent_size = Tab");
+    assert!(diagnostics.is_empty());
+    assert_eq!(section.sections()[0].properties.indent_size, Value(IndentSize::Tab));
+    assert_eq!(section.to_string(), "[*]\nindent_size = tab\n");
+    let section = EditorConfig::parse("[*]\nindent_size = 4\nindent_size = tab");
+    assert_eq!(section.sections()[0].properties.indent_size, Value(IndentSize::Tab));
+    assert_eq!("tab".parse(), Ok(IndentSize::Tab));
+
+    let resolved = editor_config.resolve(Path::new("a.rs"));
+    assert_eq!(resolved.indent_size, Value(IndentSize::Tab));
+    let resolved = editor_config.resolve_with_spec_defaults(Path::new("a.rs"));
+    assert_eq!(
+        (resolved.indent_size, resolved.tab_width),
+        (Value(IndentSize::Number(8)), Value(8))
+    );
+
+    let resolved = editor_config.resolve_with_spec_defaults(Path::new("a.md"));
+    assert_eq!(
+        (resolved.indent_size, resolved.tab_width),
+        (Value(IndentSize::Number(2)), Value(2))
+    );
+
+    let resolved = editor_config.resolve_with_spec_defaults(Path::new("a.txt"));
+    assert_eq!(
+        (resolved.indent_size, resolved.tab_width),
+        (Value(IndentSize::Number(8)), Value(8))
+    );
 }